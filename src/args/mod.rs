@@ -1,55 +1,325 @@
-use syn::{parenthesized, parse::{Parse, ParseStream}, spanned::Spanned, token, Error, Ident, Result, Token, UseTree};
+use syn::{
+    parenthesized, parse::{Parse, ParseStream}, parse_quote, punctuated::Punctuated, spanned::Spanned, token, 
+    AngleBracketedGenericArguments, Error, Expr, Generics, Ident, LitStr, Meta, Result, Token, Type, UseTree, Visibility
+};
 use std::collections::VecDeque;
-use crate::PathWithoutArgs;
+use crate::{variant_idents, NameCase, PathWithoutArgs};
 
 
 pub struct Args {
-    pub item_imports_for_async: Vec<ItemImport>,
-    pub item_imports_for_sync: Vec<ItemImport>
+    pub for_async: VariantArgs,
+    pub for_sync: VariantArgs,
+    pub fn_args: FnArgs,
 }
 
+/// sync, async のどちらか一方に適用される引数
+#[derive(Clone, Default)]
+pub struct VariantArgs {
+    pub item_imports: Vec<ItemImport>,
+    pub path_rewrites: Vec<PathRewrite>,
+    pub expr_rewrites: Vec<ExprRewrite>,
+    pub type_aliases: Vec<TypeAlias>,
+    /// 生成される名前の書式 ("Blocking{}" など)。項目の名前の書き方 (NameCase) に合うものだけを受け付ける
+    pub name: Option<LitStr>,
+    pub vis: Option<Visibility>,
+    /// 生成される項目に付ける #[cfg(...)] の条件
+    pub cfg: Option<Meta>,
+    /// cfg を docs.rs で表示するための #[cfg_attr(docsrs, doc(cfg(...)))] も付ける
+    pub doc_cfg: bool,
+}
+
+/// 関数の扱いについての引数。sync, async の両方に適用される
+#[derive(Clone, Copy, Default)]
+pub struct FnArgs {
+    /// 印のない関数に使う印
+    pub default_marker: Option<FnMarker>,
+    /// 印のない関数の印を本体から推測する。default より優先する
+    pub infer_marker: bool,
+    /// コンテナ内の async になる関数の呼び出しに .await を付ける
+    pub auto_await: bool,
+    /// async fn を印のない関数のこの印として扱う。None の場合は async fn をエラーにする
+    pub async_fn_marker: Option<FnMarker>,
+}
+
+/// 関数に付ける #[maybe_async] などの印
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum FnMarker {
+    MaybeAsync,
+    AlwaysSync,
+    AlwaysAsync,
+}
+
+impl VariantArgs {
+
+    /// self を優先して base と合わせる。
+    /// 同じ名前を持ち込む use や type は self のものだけを残す。
+    pub fn merged_with(mut self, base: &VariantArgs) -> VariantArgs {
+        let mut names = Vec::new();
+        for i in &self.item_imports {
+            if let Some(name) = i.name() {
+                names.push(name.clone());
+            }
+        }
+        for t in &self.type_aliases {
+            names.push(t.ident.clone());
+        }
+
+        for i in &base.item_imports {
+            if i.name().is_none_or(|n| !names.contains(n)) {
+                self.item_imports.push(i.clone());
+            }
+        }
+        for t in &base.type_aliases {
+            if !names.contains(&t.ident) {
+                self.type_aliases.push(t.clone());
+            }
+        }
+        self.path_rewrites.extend(base.path_rewrites.iter().cloned());
+        self.expr_rewrites.extend(base.expr_rewrites.iter().cloned());
+        self.name = self.name.or_else(|| base.name.clone());
+        self.vis = self.vis.or_else(|| base.vis.clone());
+        self.cfg = self.cfg.or_else(|| base.cfg.clone());
+        self.doc_cfg |= base.doc_cfg;
+
+        self
+    }
+}
+
+/// is_glob が true の場合は path::* を表す。
+/// glob は use として注入されるだけで、シグネチャやドキュメントの名前の置換には使われない。
+#[derive(Clone)]
 pub struct ItemImport {
     pub path: PathWithoutArgs,
     pub alias: Option<Ident>,
+    pub is_glob: bool,
+}
+
+impl ItemImport {
+
+    /// 持ち込まれる名前。glob の場合は None
+    pub fn name(&self) -> Option<&Ident> {
+        match self.is_glob {
+            true => None,
+            false => self.alias.as_ref().or_else(|| self.path.last_ident()),
+        }
+    }
+}
+
+/// from で始まるパスの from の部分を to に置き換える
+#[derive(Clone)]
+pub struct PathRewrite {
+    pub from: PathWithoutArgs,
+    pub to: PathWithoutArgs,
+}
+
+/// 式の末尾の from に一致するメソッド呼び出しなどの並びを to に置き換える
+#[derive(Clone)]
+pub struct ExprRewrite {
+    pub from: Vec<PostfixOp>,
+    pub to: Vec<PostfixOp>,
+}
+
+/// ident<generics> を ty に置き換える。
+/// ジェネリクスの引数は位置で対応させて ty に埋め込む。
+#[derive(Clone)]
+pub struct TypeAlias {
+    pub ident: Ident,
+    pub generics: Generics,
+    pub ty: Type,
+}
+
+#[derive(Clone)]
+pub enum PostfixOp {
+    MethodCall {
+        method: Ident,
+        turbofish: Option<AngleBracketedGenericArguments>,
+        args: Punctuated<Expr, Token![,]>,
+    },
+    Await,
+    Try,
 }
 
 impl Parse for Args {
 
     fn parse(input: ParseStream) -> Result<Self> {
-        let mut item_imports_for_async = Vec::new();
-        let mut item_imports_for_sync = Vec::new();
+        let mut for_async = VariantArgs::default();
+        let mut for_sync = VariantArgs::default();
+        let mut fn_args = FnArgs::default();
+        let mut paired_imports = Vec::new();
 
         while !input.is_empty() {
-            if input.peek(Token![use]) {
-                let i = parse_item_import(input)?;
-                item_imports_for_async.extend(i.item_imports_for_async);
-                item_imports_for_sync.extend(i.item_imports_for_sync);
-            } 
-            else {
-                return Err(input.error("expected `use`"))
+            if parse_common_arg(input, &mut for_sync, &mut for_async, &mut paired_imports)? {
+                parse_separator(input)?;
+                continue;
             }
 
-            if input.peek(Token![,]) {
-                input.parse::<Token![,]>()?;
+            if peek_ident(input, "sync_vis") {
+                for_sync.vis = Some(parse_vis(input)?);
+            }
+            else if peek_ident(input, "async_vis") {
+                for_async.vis = Some(parse_vis(input)?);
             }
-            else if input.peek(Token![;]) {
-                input.parse::<Token![;]>()?;
+            else if peek_ident(input, "cfg_sync") {
+                for_sync.cfg = Some(parse_cfg(input)?);
             }
+            else if peek_ident(input, "cfg_async") {
+                for_async.cfg = Some(parse_cfg(input)?);
+            }
+            // async_if = feature = "async" は元の名前のままどちらか一方だけを生成する。
+            // 名前の書式と cfg を指定したのと同じになる
+            else if peek_ident(input, "async_if") {
+                let span = input.span();
+                let cfg = parse_cfg(input)?;
+                for_sync.name = Some(LitStr::new("{}", span));
+                for_async.name = Some(LitStr::new("{}", span));
+                for_sync.cfg = Some(parse_quote! { not(#cfg) });
+                for_async.cfg = Some(cfg);
+            }
+            else if peek_ident(input, "default") {
+                fn_args.default_marker = Some(parse_default_marker(input)?);
+            }
+            else if peek_ident(input, "infer") {
+                input.parse::<Ident>()?;
+                fn_args.infer_marker = true;
+            }
+            else if peek_ident(input, "auto_await") {
+                input.parse::<Ident>()?;
+                fn_args.auto_await = true;
+            }
+            else if peek_ident(input, "async_fn") {
+                fn_args.async_fn_marker = Some(parse_async_fn_marker(input)?);
+            }
+            else if peek_ident(input, "doc_cfg") {
+                input.parse::<Ident>()?;
+                for_sync.doc_cfg = true;
+                for_async.doc_cfg = true;
+            }
+            else {
+                return Err(input.error(
+                    "expected one of: `use`, `rewrite`, `type`, `sync_name`, `async_name`, `sync_vis`, `async_vis`, \
+                    `cfg_sync`, `cfg_async`, `async_if`, `doc_cfg`, `default`, `infer`, `auto_await`, `async_fn`"
+                ))
+            }
+
+            parse_separator(input)?;
         }
 
-        Ok(Self { item_imports_for_async, item_imports_for_sync })
+        resolve_paired_imports(paired_imports, &mut for_sync, &mut for_async)?;
+
+        Ok(Self { for_async, for_sync, fn_args })
     }
 }
 
+/// #[maybe_async(...)] などの関数の印に書く、関数ごとの引数。
+/// 生成される項目全体に関わる可視性や cfg、印の決め方などは受け付けない
+pub struct FnMarkerArgs {
+    pub for_async: VariantArgs,
+    pub for_sync: VariantArgs,
+}
 
-struct ItemImports {
-    item_imports_for_async: Vec<ItemImport>,
-    item_imports_for_sync: Vec<ItemImport>,
+impl Parse for FnMarkerArgs {
+
+    fn parse(input: ParseStream) -> Result<Self> {
+        let mut for_async = VariantArgs::default();
+        let mut for_sync = VariantArgs::default();
+        let mut paired_imports = Vec::new();
+
+        while !input.is_empty() {
+            if !parse_common_arg(input, &mut for_sync, &mut for_async, &mut paired_imports)? {
+                return Err(input.error(
+                    "expected one of: `use`, `rewrite`, `type`, `sync_name`, `async_name`"
+                ))
+            }
+
+            parse_separator(input)?;
+        }
+
+        resolve_paired_imports(paired_imports, &mut for_sync, &mut for_async)?;
+
+        Ok(Self { for_async, for_sync })
+    }
 }
 
-fn parse_item_import(input: ParseStream) -> Result<ItemImports> {
-    input.parse::<Token![use]>()?;
+/// #[sync_async(...)] と関数の印の両方で使える引数を読む。読んだ場合は true を返す
+fn parse_common_arg(
+    input: ParseStream, 
+    for_sync: &mut VariantArgs, 
+    for_async: &mut VariantArgs,
+    paired_imports: &mut Vec<PairedImport>,
+) -> Result<bool> {
+
+    if input.peek(Token![use]) {
+        let i = parse_item_import(input)?;
+        for_async.item_imports.extend(i.item_imports_for_async);
+        for_sync.item_imports.extend(i.item_imports_for_sync);
+        paired_imports.extend(i.paired_imports);
+    } 
+    else if peek_ident(input, "rewrite") {
+        let r = parse_rewrite(input)?;
+        for_async.path_rewrites.extend(r.path_rewrites_for_async);
+        for_sync.path_rewrites.extend(r.path_rewrites_for_sync);
+        for_async.expr_rewrites.extend(r.expr_rewrites_for_async);
+        for_sync.expr_rewrites.extend(r.expr_rewrites_for_sync);
+    }
+    else if input.peek(Token![type]) {
+        let t = parse_type_alias(input)?;
+        for_async.type_aliases.extend(t.type_aliases_for_async);
+        for_sync.type_aliases.extend(t.type_aliases_for_sync);
+    }
+    else if peek_ident(input, "sync_name") {
+        for_sync.name = Some(parse_name_template(input)?);
+    }
+    else if peek_ident(input, "async_name") {
+        for_async.name = Some(parse_name_template(input)?);
+    }
+    else {
+        return Ok(false)
+    }
 
+    Ok(true)
+}
+
+fn parse_separator(input: ParseStream) -> Result<()> {
+    if input.peek(Token![,]) {
+        input.parse::<Token![,]>()?;
+    }
+    else if input.peek(Token![;]) {
+        input.parse::<Token![;]>()?;
+    }
+    Ok(())
+}
+
+/// use(paired) の名前は sync_name などをすべて読んでから決める
+fn resolve_paired_imports(
+    paired_imports: Vec<PairedImport>,
+    for_sync: &mut VariantArgs, 
+    for_async: &mut VariantArgs,
+) -> Result<()> {
+
+    for paired in paired_imports {
+        let (sync_import, async_import) = paired.resolve(for_sync, for_async)?;
+        for_sync.item_imports.push(sync_import);
+        for_async.item_imports.push(async_import);
+    }
+    Ok(())
+}
+
+fn peek_ident(input: ParseStream, ident: &str) -> bool {
+    input.fork().parse::<Ident>().is_ok_and(|i| i == ident)
+}
+
+fn peek_paired(input: ParseStream) -> bool {
+    fn parse_paren_ident(input: ParseStream) -> Result<Ident> {
+        let content;
+        parenthesized!(content in input);
+        content.parse()
+    }
+
+    input.peek(token::Paren) && parse_paren_ident(&input.fork()).is_ok_and(|i| i == "paired")
+}
+
+/// `(if_async)` や `(if_sync)` を読み、(async で有効か, sync で有効か) を返す
+fn parse_target_variant(input: ParseStream) -> Result<(bool, bool)> {
     let mut available_for_async = true;
     let mut available_for_sync = true;
 
@@ -69,6 +339,80 @@ fn parse_item_import(input: ParseStream) -> Result<ItemImports> {
         }
     }
 
+    Ok((available_for_async, available_for_sync))
+}
+
+
+struct ItemImports {
+    item_imports_for_async: Vec<ItemImport>,
+    item_imports_for_sync: Vec<ItemImport>,
+    paired_imports: Vec<PairedImport>,
+}
+
+/// use(paired) crate::net::Connection のように、生成された sync, async の項目を対にして持ち込む
+struct PairedImport {
+    path: Vec<Ident>,
+    alias: Option<Ident>,
+    is_glob: bool,
+    is_absolute: bool,
+}
+
+impl PairedImport {
+
+    /// use(paired) crate::net::Connection は
+    /// crate::net::SyncConnection as Connection と crate::net::AsyncConnection as Connection に、
+    /// use(paired) crate::prelude::* は crate::sync_prelude::* と crate::async_prelude::* になる。
+    /// 持ち込む項目も同じ sync_name, async_name で生成されているものとして名前を作る
+    fn resolve(self, args_for_sync: &VariantArgs, args_for_async: &VariantArgs) -> Result<(ItemImport, ItemImport)> {
+        let mut sync_path = self.path.clone();
+        let mut async_path = self.path;
+        let ident = sync_path.pop().unwrap();
+        async_path.pop();
+
+        // 書式が名前の書き方に合わない場合 ("Blocking{}" と utils など) は対の名前を作れない
+        let (sync_ident, async_ident) = variant_idents(&ident, NameCase::of(&ident), args_for_sync, args_for_async)
+            .map_err(|_| Error::new(
+                ident.span(), 
+                format!(
+                    "cannot derive the paired names of `{ident}` from `sync_name` / `async_name`; \
+                    import them with `use(if_sync)` and `use(if_async)` instead"
+                )
+            ))?;
+        sync_path.push(sync_ident);
+        async_path.push(async_ident);
+
+        let alias = match self.is_glob {
+            true => None,
+            false => Some(self.alias.unwrap_or(ident)),
+        };
+        let sync_import = ItemImport { 
+            path: PathWithoutArgs::from_idents(sync_path, self.is_absolute), 
+            alias: alias.clone(),
+            is_glob: self.is_glob,
+        };
+        let async_import = ItemImport { 
+            path: PathWithoutArgs::from_idents(async_path, self.is_absolute), 
+            alias,
+            is_glob: self.is_glob,
+        };
+        Ok((sync_import, async_import))
+    }
+}
+
+fn parse_item_import(input: ParseStream) -> Result<ItemImports> {
+    input.parse::<Token![use]>()?;
+
+    let is_paired = peek_paired(input);
+    let (available_for_async, available_for_sync) = match is_paired {
+        true => {
+            let content;
+            parenthesized!(content in input);
+            content.parse::<Ident>()?;
+            (true, true)
+        }
+        false => parse_target_variant(input)?
+    };
+
     let is_absolute_paths = match input.peek(Token![::]) {
         true => {
             input.parse::<Token![::]>()?;
@@ -94,31 +438,230 @@ fn parse_item_import(input: ParseStream) -> Result<ItemImports> {
             },
             UseTree::Name(i) => {
                 parent.push(i.ident);
-                paths_and_aliases.push((parent, None))
+                paths_and_aliases.push((parent, None, false))
             },
             UseTree::Rename(i) => {
                 parent.push(i.ident);
-                paths_and_aliases.push((parent, Some(i.rename)))
+                paths_and_aliases.push((parent, Some(i.rename), false))
+            },
+            UseTree::Glob(i) => {
+                if parent.is_empty() {
+                    return Err(Error::new(i.span(), "expected a path before `*`"))
+                }
+                paths_and_aliases.push((parent, None, true))
             },
-            UseTree::Glob(i) => return Err(Error::new(i.span(), "a glob import: `*` is not allowed")),
          }
     }
 
     let mut item_imports_for_async = Vec::new();
     let mut item_imports_for_sync = Vec::new();
-    for (path, alias) in paths_and_aliases {
+    let mut paired_imports = Vec::new();
+    for (path, alias, is_glob) in paths_and_aliases {
+        if is_paired {
+            paired_imports.push(PairedImport { path, alias, is_glob, is_absolute: is_absolute_paths });
+            continue;
+        }
+
         let path = PathWithoutArgs::from_idents(path, is_absolute_paths);
         if available_for_async && available_for_sync {
-            item_imports_for_async.push(ItemImport { path: path.clone(), alias: alias.clone()});
-            item_imports_for_sync.push(ItemImport { path, alias });
+            item_imports_for_async.push(ItemImport { path: path.clone(), alias: alias.clone(), is_glob });
+            item_imports_for_sync.push(ItemImport { path, alias, is_glob });
         }
         else if available_for_async {
-            item_imports_for_async.push(ItemImport { path, alias });
+            item_imports_for_async.push(ItemImport { path, alias, is_glob });
         }
         else if available_for_sync {
-            item_imports_for_sync.push(ItemImport { path, alias });
+            item_imports_for_sync.push(ItemImport { path, alias, is_glob });
         }
     }
 
-    Ok(ItemImports { item_imports_for_async, item_imports_for_sync })
-}
\ No newline at end of file
+    Ok(ItemImports { item_imports_for_async, item_imports_for_sync, paired_imports })
+}
+
+
+struct Rewrites {
+    path_rewrites_for_async: Vec<PathRewrite>,
+    path_rewrites_for_sync: Vec<PathRewrite>,
+    expr_rewrites_for_async: Vec<ExprRewrite>,
+    expr_rewrites_for_sync: Vec<ExprRewrite>,
+}
+
+// rewrite(if_sync) tokio::fs => std::fs
+// rewrite(if_sync) .lock().await => .lock().unwrap()
+fn parse_rewrite(input: ParseStream) -> Result<Rewrites> {
+    input.parse::<Ident>()?;
+
+    let (available_for_async, available_for_sync) = parse_target_variant(input)?;
+
+    let mut rewrites = Rewrites {
+        path_rewrites_for_async: Vec::new(),
+        path_rewrites_for_sync: Vec::new(),
+        expr_rewrites_for_async: Vec::new(),
+        expr_rewrites_for_sync: Vec::new(),
+    };
+
+    if input.peek(Token![.]) || input.peek(Token![?]) {
+        let from = parse_postfix_ops(input)?;
+        input.parse::<Token![=>]>()?;
+        let to = parse_postfix_ops(input)?;
+
+        if available_for_async {
+            rewrites.expr_rewrites_for_async.push(ExprRewrite { from: from.clone(), to: to.clone() });
+        }
+        if available_for_sync {
+            rewrites.expr_rewrites_for_sync.push(ExprRewrite { from, to });
+        }
+    }
+    else {
+        let from: PathWithoutArgs = input.parse()?;
+        input.parse::<Token![=>]>()?;
+        let to: PathWithoutArgs = input.parse()?;
+
+        if available_for_async {
+            rewrites.path_rewrites_for_async.push(PathRewrite { from: from.clone(), to: to.clone() });
+        }
+        if available_for_sync {
+            rewrites.path_rewrites_for_sync.push(PathRewrite { from, to });
+        }
+    }
+
+    Ok(rewrites)
+}
+
+// sync_name = "Blocking{}"
+fn parse_name_template(input: ParseStream) -> Result<LitStr> {
+    input.parse::<Ident>()?;
+    input.parse::<Token![=]>()?;
+    let lit: LitStr = input.parse()?;
+
+    // {} を適当な名前で置き換えて識別子になるか確かめる
+    let template = lit.value();
+    if syn::parse_str::<Ident>(&template.replace("{}", "Name")).is_err() {
+        return Err(Error::new(lit.span(), "expected a name template such as \"Blocking{}\""))
+    }
+
+    Ok(lit)
+}
+
+// sync_vis = pub(crate)
+fn parse_vis(input: ParseStream) -> Result<Visibility> {
+    input.parse::<Ident>()?;
+    input.parse::<Token![=]>()?;
+    input.parse()
+}
+
+// cfg_sync = feature = "blocking"
+// cfg_async = any(feature = "tokio", feature = "async-std")
+fn parse_cfg(input: ParseStream) -> Result<Meta> {
+    input.parse::<Ident>()?;
+    input.parse::<Token![=]>()?;
+    input.parse()
+}
+
+// default = maybe_async
+fn parse_default_marker(input: ParseStream) -> Result<FnMarker> {
+    input.parse::<Ident>()?;
+    input.parse::<Token![=]>()?;
+    let marker: Ident = input.parse()?;
+
+    if marker == "maybe_async" {
+        Ok(FnMarker::MaybeAsync)
+    }
+    else if marker == "always_sync" {
+        Ok(FnMarker::AlwaysSync)
+    }
+    else if marker == "always_async" {
+        Ok(FnMarker::AlwaysAsync)
+    }
+    else {
+        Err(Error::new(marker.span(), "expected one of: `maybe_async`, `always_sync`, `always_async`"))
+    }
+}
+
+// async_fn = maybe
+fn parse_async_fn_marker(input: ParseStream) -> Result<FnMarker> {
+    input.parse::<Ident>()?;
+    input.parse::<Token![=]>()?;
+    let marker: Ident = input.parse()?;
+
+    if marker == "maybe" {
+        Ok(FnMarker::MaybeAsync)
+    }
+    else if marker == "always" {
+        Ok(FnMarker::AlwaysAsync)
+    }
+    else {
+        Err(Error::new(marker.span(), "expected one of: `maybe`, `always`"))
+    }
+}
+
+struct TypeAliases {
+    type_aliases_for_async: Vec<TypeAlias>,
+    type_aliases_for_sync: Vec<TypeAlias>,
+}
+
+// type(if_async) Lock<T> = tokio::sync::RwLock<T>
+// type Bytes = Vec<u8>
+fn parse_type_alias(input: ParseStream) -> Result<TypeAliases> {
+    input.parse::<Token![type]>()?;
+
+    let (available_for_async, available_for_sync) = parse_target_variant(input)?;
+
+    let ident: Ident = input.parse()?;
+    let generics: Generics = input.parse()?;
+    input.parse::<Token![=]>()?;
+    let ty: Type = input.parse()?;
+
+    let mut type_aliases = TypeAliases {
+        type_aliases_for_async: Vec::new(),
+        type_aliases_for_sync: Vec::new(),
+    };
+    let alias = TypeAlias { ident, generics, ty };
+    if available_for_async {
+        type_aliases.type_aliases_for_async.push(alias.clone());
+    }
+    if available_for_sync {
+        type_aliases.type_aliases_for_sync.push(alias);
+    }
+
+    Ok(type_aliases)
+}
+
+// .lock().await?
+fn parse_postfix_ops(input: ParseStream) -> Result<Vec<PostfixOp>> {
+    let mut ops = Vec::new();
+
+    loop {
+        if input.peek(Token![?]) {
+            input.parse::<Token![?]>()?;
+            ops.push(PostfixOp::Try);
+        }
+        else if input.peek(Token![.]) && !input.peek(Token![..]) {
+            input.parse::<Token![.]>()?;
+            if input.peek(Token![await]) {
+                input.parse::<Token![await]>()?;
+                ops.push(PostfixOp::Await);
+            }
+            else {
+                let method: Ident = input.parse()?;
+                let turbofish = match input.peek(Token![::]) {
+                    true => Some(AngleBracketedGenericArguments::parse_turbofish(input)?),
+                    false => None,
+                };
+                let content;
+                parenthesized!(content in input);
+                let args = content.parse_terminated(Expr::parse, Token![,])?;
+                ops.push(PostfixOp::MethodCall { method, turbofish, args });
+            }
+        }
+        else {
+            break;
+        }
+    }
+
+    if ops.is_empty() {
+        return Err(input.error("expected one of: `.method()`, `.await`, `?`"))
+    }
+
+    Ok(ops)
+}
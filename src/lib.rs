@@ -4,8 +4,11 @@ mod utils;
 
 use crate::{args::*, map::*, utils::*};
 use proc_macro2::TokenStream;
-use quote::{quote, format_ident};
-use syn::{parse_macro_input, spanned::Spanned, Error, Item, ItemImpl, ItemMod, ItemStruct, Result};
+use quote::quote;
+use syn::{
+    parse_macro_input, spanned::Spanned, Error, Fields, Ident, Item, ItemConst, ItemEnum, ItemFn, ItemImpl, ItemMod, 
+    ItemStatic, ItemStruct, ItemTrait, ItemType, ItemUnion, Result
+};
 
 
 #[proc_macro_attribute]
@@ -15,150 +18,451 @@ pub fn sync_async(attr: proc_macro::TokenStream, item: proc_macro::TokenStream)
     
     match item {
         Item::Mod(item) => expand_mod(item, args),
+        Item::Fn(item) => expand_fn(item, args),
         Item::Impl(item) => expand_impl(item, args),
         Item::Struct(item) => expand_struct(item, args),
-        item => Err(Error::new(item.span(), "expected one of: impl, mod, struct"))
+        Item::Enum(item) => expand_enum(item, args),
+        Item::Union(item) => expand_union(item, args),
+        Item::Trait(item) => expand_trait(item, args),
+        Item::Type(item) => expand_type(item, args),
+        Item::Const(item) => expand_const(item, args),
+        Item::Static(item) => expand_static(item, args),
+        item => Err(Error::new(item.span(), "expected one of: impl, mod, fn, struct, enum, union, trait, type, const, static"))
     }
     .map(Into::into)
     .unwrap_or_else(|r| r.into_compile_error().into())
 }
 
 
+/// 生成される名前と、元の名前をその別名として持つ引数を返す。
+/// 別名があることで、本体やドキュメントの元の名前が同じバリアントを指すようになる。
+fn variant_idents_and_args(
+    ident: &Ident,
+    case: NameCase,
+    args: Args
+) -> Result<(Ident, Ident, VariantArgs, VariantArgs)> {
+
+    let (sync_ident, async_ident) = variant_idents(ident, case, &args.for_sync, &args.for_async)?;
+    let mut args_for_sync = args.for_sync;
+    let mut args_for_async = args.for_async;
+    push_self_alias(&mut args_for_sync, ident, PathWithoutArgs::from_idents(vec![sync_ident.clone()], false));
+    push_self_alias(&mut args_for_async, ident, PathWithoutArgs::from_idents(vec![async_ident.clone()], false));
+
+    Ok((sync_ident, async_ident, args_for_sync, args_for_async))
+}
+
+fn push_self_alias(args: &mut VariantArgs, ident: &Ident, path: PathWithoutArgs) {
+    // async_name = "{}" のように名前が変わらない場合は別名はいらない
+    if path.is_ident(ident) {
+        return;
+    }
+    args.item_imports.push(ItemImport { path, alias: Some(ident.clone()), is_glob: false });
+}
+
 fn expand_mod(item: ItemMod, args: Args) -> Result<TokenStream> {
-    let vis = item.vis;
     let _unsafety = item.unsafety;
     let (sync_imports, async_imports) = {
-        let si = item_uses_from_item_imports(&args.item_imports_for_sync);
-        let ai = item_uses_from_item_imports(&args.item_imports_for_async);
+        let si = items_from_variant_args(&args.for_sync);
+        let ai = items_from_variant_args(&args.for_async);
         (si, ai)
     };
-    let (sync_ident, async_ident, item_imports_for_sync, item_imports_for_async) = {
-        let i = &item.ident;
-        let s = format_ident!("sync_{}", i);
-        let a = format_ident!("async_{}", i);
-    
-        let sp = PathWithoutArgs::from_idents(vec![s.clone()], false);
-        let ap = PathWithoutArgs::from_idents(vec![a.clone()], false);
-        let mut si = args.item_imports_for_sync;
-        let mut ai = args.item_imports_for_async;
-        si.push(ItemImport { path: sp, alias: Some(i.clone()) });
-        ai.push(ItemImport { path: ap, alias: Some(i.clone()) });
-        
-        (s, a, si, ai)
+    // ネストしたモジュールにも同じ use と type を注入する
+    let (sync_ctx, async_ctx) = {
+        let s = ContainerContext { mod_items: sync_imports.clone(), ..ContainerContext::new(args.fn_args) };
+        let a = ContainerContext { mod_items: async_imports.clone(), ..ContainerContext::new(args.fn_args) };
+        let items = item.content.as_ref().map(|i| i.1.as_slice()).unwrap_or_default();
+        mod_context(items, &s, &a)
     };
+    let (sync_ident, async_ident, args_for_sync, args_for_async) = 
+        variant_idents_and_args(&item.ident, NameCase::Snake, args)?;
+    let (sync_vis, async_vis) = variant_vis(&item.vis, &args_for_sync, &args_for_async);
     let (sync_attrs, async_attrs) = {
-        let a = map_attrs(item.attrs, &item_imports_for_sync, &item_imports_for_async)?;
+        let a = map_container_attrs(item.attrs, &args_for_sync, &args_for_async, &sync_ctx, &async_ctx)?;
         (a.sync_attrs, a.async_attrs)
     };
+    let (sync_cfg, async_cfg) = {
+        let s = cfg_attrs_from_variant_args(&args_for_sync);
+        let a = cfg_attrs_from_variant_args(&args_for_async);
+        (s, a)
+    };
     let (sync_items, async_items) = {
         let i = item.content.map(|i| i.1).unwrap_or_else(|| Vec::with_capacity(0));
-        // use を使うので型の置換はなしでいい
-        let i = map_mod_items(i, &[], &[])?;
+        // use や type を使うので名前の置換はなしでいい (書き換えの規則だけ使う)
+        let sa = VariantArgs { item_imports: Vec::new(), type_aliases: Vec::new(), ..args_for_sync.clone() };
+        let aa = VariantArgs { item_imports: Vec::new(), type_aliases: Vec::new(), ..args_for_async.clone() };
+        let i = map_mod_items(i, &sa, &aa, &sync_ctx, &async_ctx)?;
         (i.sync_items, i.async_items)
     };
     let semi = item.semi;
 
     Ok(quote! {
+        #(#sync_cfg)*
         #(#sync_attrs)*
-        #[allow(unused_imports)]
-        #vis mod #sync_ident {
+        #sync_vis mod #sync_ident {
             #(#sync_imports)*
             #(#sync_items)*
         }#semi
 
+        #(#async_cfg)*
         #(#async_attrs)*
-        #[allow(unused_imports)]
-        #vis mod #async_ident {
+        #async_vis mod #async_ident {
             #(#async_imports)*
             #(#async_items)*
         }#semi
     })
 }
 
-fn expand_impl(item: ItemImpl, args: Args) -> Result<TokenStream> {
-    if let Some(trait_) = item.trait_ {
-        return Err(Error::new(trait_.2.span(), "unsupported trait impl"))
-    }
+fn expand_fn(item: ItemFn, args: Args) -> Result<TokenStream> {
+    let ctx = ContainerContext::new(args.fn_args);
+    // 再帰呼び出しが同じバリアントを指すように、関数名を別名として本体に注入する
+    let (sync_ident, async_ident, args_for_sync, args_for_async) = 
+        variant_idents_and_args(&item.sig.ident, NameCase::Snake, args)?;
+    let (sync_vis, async_vis) = variant_vis(&item.vis, &args_for_sync, &args_for_async);
+    let mut sync_item = item.clone();
+    let mut async_item = item;
+    sync_item.sig.ident = sync_ident;
+    async_item.sig.ident = async_ident;
+    sync_item.vis = sync_vis;
+    async_item.vis = async_vis;
+    let (sync_cfg, async_cfg) = {
+        let s = cfg_attrs_from_variant_args(&args_for_sync);
+        let a = cfg_attrs_from_variant_args(&args_for_async);
+        (s, a)
+    };
+    let sync_item = map_mod_fn(sync_item, false, &args_for_sync, &ctx)?;
+    let async_item = map_mod_fn(async_item, true, &args_for_async, &ctx)?;
 
-    let (item_imports_for_sync, item_imports_for_async) = {
-        let mut si = args.item_imports_for_sync;
-        let mut ai = args.item_imports_for_async;
-        let i = map_type_to_ident(&item.self_ty)?;
+    Ok(quote! {
+        #(#sync_cfg)*
+        #sync_item
 
-        si.push(ItemImport { alias: Some(syn::parse_str(&i.ident)?), path: syn::parse_str(&i.sync_ident)? });
-        ai.push(ItemImport { alias: Some(syn::parse_str(&i.ident)?), path: syn::parse_str(&i.async_ident)? });
+        #(#async_cfg)*
+        #async_item
+    })
+}
+
+fn expand_impl(item: ItemImpl, args: Args) -> Result<TokenStream> {
+    let (sync_ctx, async_ctx) = {
+        let ctx = ContainerContext::new(args.fn_args);
+        impl_context(&item.items, &item.self_ty, &ctx, &ctx)
+    };
+    let (args_for_sync, args_for_async) = {
+        let mut si = args.for_sync;
+        let mut ai = args.for_async;
+        let i = map_type_to_ident(&item.self_ty, &si, &ai)?;
+
+        let ident = syn::parse_str(&i.ident)?;
+        push_self_alias(&mut si, &ident, syn::parse_str(&i.sync_ident)?);
+        push_self_alias(&mut ai, &ident, syn::parse_str(&i.async_ident)?);
         (si, ai)
     };
 
     let (sync_attrs, async_attrs) = {
-        let a = map_attrs(item.attrs, &item_imports_for_sync, &item_imports_for_async)?;
+        let a = map_container_attrs(item.attrs, &args_for_sync, &args_for_async, &sync_ctx, &async_ctx)?;
         (a.sync_attrs, a.async_attrs)
     };
+    let (sync_cfg, async_cfg) = {
+        let s = cfg_attrs_from_variant_args(&args_for_sync);
+        let a = cfg_attrs_from_variant_args(&args_for_async);
+        (s, a)
+    };
     let _defaultness = item.defaultness;
     let unsafety = item.unsafety;
     let (sync_generics, async_generics) = {
-        let g = map_generics(item.generics, &item_imports_for_sync, &item_imports_for_async)?;
+        let g = map_generics(item.generics, &args_for_sync, &args_for_async)?;
         (g.sync_generics, g.async_generics)
     };
+    let (sync_trait, async_trait) = match item.trait_ {
+        None => (None, None),
+        Some((bang, path, for_token)) => {
+            let p = map_path(path, &args_for_sync, &args_for_async)?;
+            let (sp, ap) = (p.sync_path, p.async_path);
+            (Some(quote! { #bang #sp #for_token }), Some(quote! { #bang #ap #for_token }))
+        }
+    };
     let (sync_self_ty, async_self_ty) = {
-        let t = map_type(*item.self_ty)?;
+        let t = map_type(*item.self_ty, &args_for_sync, &args_for_async)?;
         (t.sync_ty, t.async_ty)
     };
     let (sync_items, async_items) = {
-        let i = map_impl_items(item.items, &item_imports_for_sync, &item_imports_for_async)?;
+        let i = map_impl_items(item.items, &args_for_sync, &args_for_async, &sync_ctx, &async_ctx)?;
         (i.sync_items, i.async_items)
     };
+    let (sync_where_clause, async_where_clause) = (&sync_generics.where_clause, &async_generics.where_clause);
 
     Ok(quote! {
+        #(#sync_cfg)*
         #(#sync_attrs)*
-        #[allow(unused_imports)]
-        #unsafety impl #sync_generics #sync_self_ty {
+        #unsafety impl #sync_generics #sync_trait #sync_self_ty #sync_where_clause {
             #(#sync_items)*
         }
 
+        #(#async_cfg)*
         #(#async_attrs)*
-        #[allow(unused_imports)]
-        #unsafety impl #async_generics #async_self_ty {
+        #unsafety impl #async_generics #async_trait #async_self_ty #async_where_clause {
             #(#async_items)*
         }
     })
 }
 
 fn expand_struct(item: ItemStruct, args: Args) -> Result<TokenStream> {
-    let vis = item.vis;
-    let (sync_ident, async_ident, item_imports_for_sync, item_imports_for_async) = {
-        let i = &item.ident;
-        let s = format_ident!("Sync{}", i);
-        let a = format_ident!("Async{}", i);
-    
-        let sp = PathWithoutArgs::from_idents(vec![s.clone()], false);
-        let ap = PathWithoutArgs::from_idents(vec![a.clone()], false);
-        let mut si = args.item_imports_for_sync;
-        let mut ai = args.item_imports_for_async;
-        si.push(ItemImport { path: sp, alias: Some(i.clone()) });
-        ai.push(ItemImport { path: ap, alias: Some(i.clone()) });
-        
-        (s, a, si, ai)
-    };
+    let (sync_ident, async_ident, args_for_sync, args_for_async) = 
+        variant_idents_and_args(&item.ident, NameCase::UpperCamel, args)?;
+    let (sync_vis, async_vis) = variant_vis(&item.vis, &args_for_sync, &args_for_async);
     let (sync_attrs, async_attrs) = {
-        let a = map_attrs(item.attrs, &item_imports_for_sync, &item_imports_for_async)?;
+        let a = map_attrs(item.attrs, &args_for_sync, &args_for_async)?;
         (a.sync_attrs, a.async_attrs)
     };
+    let (sync_cfg, async_cfg) = {
+        let s = cfg_attrs_from_variant_args(&args_for_sync);
+        let a = cfg_attrs_from_variant_args(&args_for_async);
+        (s, a)
+    };
     let (sync_generics, async_generics) = {
-        let g = map_generics(item.generics, &item_imports_for_sync, &item_imports_for_async)?;
+        let g = map_generics(item.generics, &args_for_sync, &args_for_async)?;
         (g.sync_generics, g.async_generics)
     };
     let (sync_fields, async_fields) = {
-        let f = map_fields(item.fields, &item_imports_for_sync, &item_imports_for_async)?;
+        let f = map_fields(item.fields, &args_for_sync, &args_for_async)?;
         (f.sync_fields, f.async_fields)
     };
     let semi = item.semi_token;
 
     Ok(quote! {
+        #(#sync_cfg)*
         #(#sync_attrs)* 
-        #vis struct #sync_ident #sync_generics #sync_fields #semi
+        #sync_vis struct #sync_ident #sync_generics #sync_fields #semi
 
+        #(#async_cfg)*
         #(#async_attrs)*      
-        #vis struct #async_ident #async_generics #async_fields #semi
-    }.into())
-}
\ No newline at end of file
+        #async_vis struct #async_ident #async_generics #async_fields #semi
+    })
+}
+
+fn expand_enum(item: ItemEnum, args: Args) -> Result<TokenStream> {
+    let (sync_ident, async_ident, args_for_sync, args_for_async) = 
+        variant_idents_and_args(&item.ident, NameCase::UpperCamel, args)?;
+    let (sync_vis, async_vis) = variant_vis(&item.vis, &args_for_sync, &args_for_async);
+    let (sync_attrs, async_attrs) = {
+        let a = map_attrs(item.attrs, &args_for_sync, &args_for_async)?;
+        (a.sync_attrs, a.async_attrs)
+    };
+    let (sync_cfg, async_cfg) = {
+        let s = cfg_attrs_from_variant_args(&args_for_sync);
+        let a = cfg_attrs_from_variant_args(&args_for_async);
+        (s, a)
+    };
+    let (sync_generics, async_generics) = {
+        let g = map_generics(item.generics, &args_for_sync, &args_for_async)?;
+        (g.sync_generics, g.async_generics)
+    };
+    let (sync_variants, async_variants) = {
+        let v = map_variants(item.variants, &args_for_sync, &args_for_async)?;
+        (v.sync_variants, v.async_variants)
+    };
+    let (sync_where_clause, async_where_clause) = (&sync_generics.where_clause, &async_generics.where_clause);
+
+    Ok(quote! {
+        #(#sync_cfg)*
+        #(#sync_attrs)*
+        #sync_vis enum #sync_ident #sync_generics #sync_where_clause {
+            #sync_variants
+        }
+
+        #(#async_cfg)*
+        #(#async_attrs)*
+        #async_vis enum #async_ident #async_generics #async_where_clause {
+            #async_variants
+        }
+    })
+}
+
+fn expand_union(item: ItemUnion, args: Args) -> Result<TokenStream> {
+    let (sync_ident, async_ident, args_for_sync, args_for_async) = 
+        variant_idents_and_args(&item.ident, NameCase::UpperCamel, args)?;
+    let (sync_vis, async_vis) = variant_vis(&item.vis, &args_for_sync, &args_for_async);
+    let (sync_attrs, async_attrs) = {
+        let a = map_attrs(item.attrs, &args_for_sync, &args_for_async)?;
+        (a.sync_attrs, a.async_attrs)
+    };
+    let (sync_cfg, async_cfg) = {
+        let s = cfg_attrs_from_variant_args(&args_for_sync);
+        let a = cfg_attrs_from_variant_args(&args_for_async);
+        (s, a)
+    };
+    let (sync_generics, async_generics) = {
+        let g = map_generics(item.generics, &args_for_sync, &args_for_async)?;
+        (g.sync_generics, g.async_generics)
+    };
+    let (sync_fields, async_fields) = {
+        let f = map_fields(Fields::Named(item.fields), &args_for_sync, &args_for_async)?;
+        (f.sync_fields, f.async_fields)
+    };
+    let (sync_where_clause, async_where_clause) = (&sync_generics.where_clause, &async_generics.where_clause);
+
+    Ok(quote! {
+        #(#sync_cfg)*
+        #(#sync_attrs)*
+        #sync_vis union #sync_ident #sync_generics #sync_where_clause #sync_fields
+
+        #(#async_cfg)*
+        #(#async_attrs)*
+        #async_vis union #async_ident #async_generics #async_where_clause #async_fields
+    })
+}
+
+fn expand_trait(item: ItemTrait, args: Args) -> Result<TokenStream> {
+    let (sync_ctx, async_ctx) = {
+        let ctx = ContainerContext::new(args.fn_args);
+        trait_context(&item.items, &item.ident, &ctx, &ctx)
+    };
+    let (sync_ident, async_ident, args_for_sync, args_for_async) = 
+        variant_idents_and_args(&item.ident, NameCase::UpperCamel, args)?;
+    let (sync_vis, async_vis) = variant_vis(&item.vis, &args_for_sync, &args_for_async);
+    let (sync_attrs, async_attrs) = {
+        let a = map_container_attrs(item.attrs, &args_for_sync, &args_for_async, &sync_ctx, &async_ctx)?;
+        (a.sync_attrs, a.async_attrs)
+    };
+    let (sync_cfg, async_cfg) = {
+        let s = cfg_attrs_from_variant_args(&args_for_sync);
+        let a = cfg_attrs_from_variant_args(&args_for_async);
+        (s, a)
+    };
+    let unsafety = item.unsafety;
+    let auto_token = item.auto_token;
+    let (sync_generics, async_generics) = {
+        let g = map_generics(item.generics, &args_for_sync, &args_for_async)?;
+        (g.sync_generics, g.async_generics)
+    };
+    let colon_token = item.colon_token;
+    let (sync_supertraits, async_supertraits) = {
+        let s = map_bounds(item.supertraits, &args_for_sync, &args_for_async)?;
+        (s.sync_bounds, s.async_bounds)
+    };
+    let (sync_items, async_items) = {
+        let i = map_trait_items(item.items, &args_for_sync, &args_for_async, &sync_ctx, &async_ctx)?;
+        (i.sync_items, i.async_items)
+    };
+    let (sync_where_clause, async_where_clause) = (&sync_generics.where_clause, &async_generics.where_clause);
+
+    Ok(quote! {
+        #(#sync_cfg)*
+        #(#sync_attrs)*
+        #sync_vis #unsafety #auto_token trait #sync_ident #sync_generics #colon_token #sync_supertraits #sync_where_clause {
+            #(#sync_items)*
+        }
+
+        #(#async_cfg)*
+        #(#async_attrs)*
+        #async_vis #unsafety #auto_token trait #async_ident #async_generics #colon_token #async_supertraits #async_where_clause {
+            #(#async_items)*
+        }
+    })
+}
+
+fn expand_type(item: ItemType, args: Args) -> Result<TokenStream> {
+    let (args_for_sync, args_for_async) = (args.for_sync, args.for_async);
+    let (sync_ident, async_ident) = variant_idents(&item.ident, NameCase::UpperCamel, &args_for_sync, &args_for_async)?;
+    let (sync_vis, async_vis) = variant_vis(&item.vis, &args_for_sync, &args_for_async);
+    let (sync_attrs, async_attrs) = {
+        let a = map_attrs(item.attrs, &args_for_sync, &args_for_async)?;
+        (a.sync_attrs, a.async_attrs)
+    };
+    let (sync_cfg, async_cfg) = {
+        let s = cfg_attrs_from_variant_args(&args_for_sync);
+        let a = cfg_attrs_from_variant_args(&args_for_async);
+        (s, a)
+    };
+    let (sync_generics, async_generics) = {
+        let g = map_generics(item.generics, &args_for_sync, &args_for_async)?;
+        (g.sync_generics, g.async_generics)
+    };
+    let (sync_ty, async_ty) = {
+        let t = map_type_by_variant_args(*item.ty, &args_for_sync, &args_for_async)?;
+        (t.sync_ty, t.async_ty)
+    };
+    let (sync_where_clause, async_where_clause) = (&sync_generics.where_clause, &async_generics.where_clause);
+
+    Ok(quote! {
+        #(#sync_cfg)*
+        #(#sync_attrs)*
+        #sync_vis type #sync_ident #sync_generics #sync_where_clause = #sync_ty;
+
+        #(#async_cfg)*
+        #(#async_attrs)*
+        #async_vis type #async_ident #async_generics #async_where_clause = #async_ty;
+    })
+}
+
+fn expand_const(item: ItemConst, args: Args) -> Result<TokenStream> {
+    // 定数は SCREAMING_SNAKE_CASE なので接頭辞もそれに合わせる
+    let (args_for_sync, args_for_async) = (args.for_sync, args.for_async);
+    let (sync_ident, async_ident) = variant_idents(&item.ident, NameCase::ScreamingSnake, &args_for_sync, &args_for_async)?;
+    let (sync_vis, async_vis) = variant_vis(&item.vis, &args_for_sync, &args_for_async);
+    let (sync_attrs, async_attrs) = {
+        let a = map_attrs(item.attrs, &args_for_sync, &args_for_async)?;
+        (a.sync_attrs, a.async_attrs)
+    };
+    let (sync_cfg, async_cfg) = {
+        let s = cfg_attrs_from_variant_args(&args_for_sync);
+        let a = cfg_attrs_from_variant_args(&args_for_async);
+        (s, a)
+    };
+    let (sync_generics, async_generics) = {
+        let g = map_generics(item.generics, &args_for_sync, &args_for_async)?;
+        (g.sync_generics, g.async_generics)
+    };
+    let (sync_ty, async_ty) = {
+        let t = map_type_by_variant_args(*item.ty, &args_for_sync, &args_for_async)?;
+        (t.sync_ty, t.async_ty)
+    };
+    let (sync_expr, async_expr) = {
+        let e = map_expr(*item.expr, &args_for_sync, &args_for_async)?;
+        (e.sync_expr, e.async_expr)
+    };
+
+    Ok(quote! {
+        #(#sync_cfg)*
+        #(#sync_attrs)*
+        #sync_vis const #sync_ident #sync_generics: #sync_ty = #sync_expr;
+
+        #(#async_cfg)*
+        #(#async_attrs)*
+        #async_vis const #async_ident #async_generics: #async_ty = #async_expr;
+    })
+}
+
+fn expand_static(item: ItemStatic, args: Args) -> Result<TokenStream> {
+    // 静的変数は SCREAMING_SNAKE_CASE なので接頭辞もそれに合わせる
+    let (args_for_sync, args_for_async) = (args.for_sync, args.for_async);
+    let (sync_ident, async_ident) = variant_idents(&item.ident, NameCase::ScreamingSnake, &args_for_sync, &args_for_async)?;
+    let (sync_vis, async_vis) = variant_vis(&item.vis, &args_for_sync, &args_for_async);
+    let (sync_attrs, async_attrs) = {
+        let a = map_attrs(item.attrs, &args_for_sync, &args_for_async)?;
+        (a.sync_attrs, a.async_attrs)
+    };
+    let (sync_cfg, async_cfg) = {
+        let s = cfg_attrs_from_variant_args(&args_for_sync);
+        let a = cfg_attrs_from_variant_args(&args_for_async);
+        (s, a)
+    };
+    let mutability = item.mutability;
+    let (sync_ty, async_ty) = {
+        let t = map_type_by_variant_args(*item.ty, &args_for_sync, &args_for_async)?;
+        (t.sync_ty, t.async_ty)
+    };
+    let (sync_expr, async_expr) = {
+        let e = map_expr(*item.expr, &args_for_sync, &args_for_async)?;
+        (e.sync_expr, e.async_expr)
+    };
+
+    Ok(quote! {
+        #(#sync_cfg)*
+        #(#sync_attrs)*
+        #sync_vis static #mutability #sync_ident: #sync_ty = #sync_expr;
+
+        #(#async_cfg)*
+        #(#async_attrs)*
+        #async_vis static #mutability #async_ident: #async_ty = #async_expr;
+    })
+}
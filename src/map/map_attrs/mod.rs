@@ -1,5 +1,5 @@
-use syn::{Attribute, Result};
-use crate::{replace_item_name_in_doc, replaces_from_item_imports, ItemImport};
+use syn::{punctuated::Punctuated, Attribute, Meta, Result, Token};
+use crate::{replace_item_name_in_doc, replaces_from_path_rewrites, replaces_from_variant_args, ContainerContext, VariantArgs};
 
 
 pub struct SyncAsyncAttrs {
@@ -9,20 +9,74 @@ pub struct SyncAsyncAttrs {
 
 pub fn map_attrs(
     attrs: Vec<Attribute>,
-    item_imports_for_sync: &[ItemImport],
-    item_imports_for_async: &[ItemImport]
+    args_for_sync: &VariantArgs,
+    args_for_async: &VariantArgs
 ) -> Result<SyncAsyncAttrs> {
 
-    let mut sync_attrs = attrs.clone();
-    let mut async_attrs = attrs;
+    let mut sync_attrs = select_variant_attrs(attrs.clone(), false)?;
+    let mut async_attrs = select_variant_attrs(attrs, true)?;
     replace_item_name_in_doc(
         sync_attrs.iter_mut(), 
-        &replaces_from_item_imports(item_imports_for_sync)
+        &replaces_from_variant_args(args_for_sync)
     );
     replace_item_name_in_doc(
         async_attrs.iter_mut(), 
-        &replaces_from_item_imports(item_imports_for_async)
+        &replaces_from_variant_args(args_for_async)
     );
 
     Ok(SyncAsyncAttrs { sync_attrs, async_attrs })
 }
+
+/// map_attrs と同じだが、中の関数の名前が変わる場合は [Foo::read] などのリンクも書き換える
+pub fn map_container_attrs(
+    attrs: Vec<Attribute>,
+    args_for_sync: &VariantArgs,
+    args_for_async: &VariantArgs,
+    ctx_for_sync: &ContainerContext,
+    ctx_for_async: &ContainerContext,
+) -> Result<SyncAsyncAttrs> {
+
+    let mut sync_attrs = select_variant_attrs(attrs.clone(), false)?;
+    let mut async_attrs = select_variant_attrs(attrs, true)?;
+    replace_doc_links(&mut sync_attrs, args_for_sync, ctx_for_sync);
+    replace_doc_links(&mut async_attrs, args_for_async, ctx_for_async);
+
+    Ok(SyncAsyncAttrs { sync_attrs, async_attrs })
+}
+
+/// 名前が変わる関数へのリンクを先に新しい名前にしてから、引数の use などで名前を置き換える。
+/// [Foo::read](Foo::read_blocking) の Foo も置き換えるため、二回に分けて行う
+pub fn replace_doc_links(attrs: &mut [Attribute], args: &VariantArgs, ctx: &ContainerContext) {
+    replace_item_name_in_doc(attrs.iter_mut(), &replaces_from_path_rewrites(&ctx.fn_renames));
+    replace_item_name_in_doc(attrs.iter_mut(), &replaces_from_variant_args(args));
+}
+
+/// #[if_sync(...)] / #[if_async(...)] を、対象のバリアントでは中身の属性に置き換え、他方のバリアントでは取り除く。
+/// 中身のない #[if_sync] / #[if_async] は文などの選択に使われるのでそのまま残す。
+pub fn select_variant_attrs(attrs: Vec<Attribute>, asyncness: bool) -> Result<Vec<Attribute>> {
+    let mut buf = Vec::with_capacity(attrs.len());
+
+    for attr in attrs {
+        let is_if_async = attr.path().is_ident("if_async");
+        let is_if_sync = attr.path().is_ident("if_sync");
+        let Meta::List(list) = &attr.meta else {
+            buf.push(attr);
+            continue;
+        };
+        if !is_if_async && !is_if_sync {
+            buf.push(attr);
+            continue;
+        }
+        if is_if_async != asyncness {
+            continue;
+        }
+
+        // #[if_sync(derive(Clone), must_use)] のように複数書ける
+        let metas = list.parse_args_with(Punctuated::<Meta, Token![,]>::parse_terminated)?;
+        for meta in metas {
+            buf.push(Attribute { meta, ..attr.clone() });
+        }
+    }
+
+    Ok(buf)
+}
@@ -0,0 +1,28 @@
+use syn::{punctuated::Punctuated, Result, Token, TypeParamBound};
+use crate::{map::replace_item_name::{replace_item_name_in_type_param_bound, replaces_from_variant_args}, VariantArgs};
+
+
+pub struct SyncAsyncBounds {
+    pub sync_bounds: Punctuated<TypeParamBound, Token![+]>,
+    pub async_bounds: Punctuated<TypeParamBound, Token![+]>
+}
+
+pub fn map_bounds(
+    bounds: Punctuated<TypeParamBound, Token![+]>,
+    args_for_sync: &VariantArgs,
+    args_for_async: &VariantArgs
+) -> Result<SyncAsyncBounds> {
+
+    let mut sync_bounds = bounds.clone();
+    let mut async_bounds = bounds;
+    let sync_replaces = replaces_from_variant_args(args_for_sync);
+    let async_replaces = replaces_from_variant_args(args_for_async);
+    for bound in &mut sync_bounds {
+        replace_item_name_in_type_param_bound(bound, &sync_replaces);
+    }
+    for bound in &mut async_bounds {
+        replace_item_name_in_type_param_bound(bound, &async_replaces);
+    }
+
+    Ok(SyncAsyncBounds { sync_bounds, async_bounds })
+}
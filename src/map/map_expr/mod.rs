@@ -0,0 +1,41 @@
+use syn::{token, Block, Expr, ExprBlock, Result, Stmt};
+use crate::{items_from_variant_args, VariantArgs};
+
+
+pub struct SyncAsyncExpr {
+    pub sync_expr: Expr,
+    pub async_expr: Expr
+}
+
+/// 式を use 文や type 文を先頭に持つブロック式で包む。
+/// const や static の初期化式の中で use や type の引数を使えるようにするため。
+pub fn map_expr(
+    expr: Expr,
+    args_for_sync: &VariantArgs,
+    args_for_async: &VariantArgs
+) -> Result<SyncAsyncExpr> {
+
+    let sync_expr = wrap_expr_with_items(expr.clone(), args_for_sync);
+    let async_expr = wrap_expr_with_items(expr, args_for_async);
+
+    Ok(SyncAsyncExpr { sync_expr, async_expr })
+}
+
+fn wrap_expr_with_items(expr: Expr, args: &VariantArgs) -> Expr {
+    let items = items_from_variant_args(args);
+    if items.is_empty() {
+        return expr;
+    }
+
+    let mut stmts = Vec::with_capacity(items.len() + 1);
+    for item in items {
+        stmts.push(Stmt::Item(item));
+    }
+    stmts.push(Stmt::Expr(expr, None));
+
+    Expr::Block(ExprBlock {
+        attrs: Vec::new(),
+        label: None,
+        block: Block { brace_token: token::Brace::default(), stmts },
+    })
+}
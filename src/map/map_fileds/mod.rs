@@ -1,5 +1,5 @@
 use syn::{Fields, Result};
-use crate::{map::replace_item_name::{replace_item_name_in_fields, replaces_from_item_imports}, ItemImport};
+use crate::{map::replace_item_name::{replace_item_name_in_fields, replaces_from_variant_args}, select_variant_attrs, VariantArgs};
 
 
 pub struct SyncAsyncFields {
@@ -9,20 +9,29 @@ pub struct SyncAsyncFields {
 
 pub fn map_fields(
     fields: Fields,
-    item_imports_for_sync: &[ItemImport],
-    item_imports_for_async: &[ItemImport]
+    args_for_sync: &VariantArgs,
+    args_for_async: &VariantArgs
 ) -> Result<SyncAsyncFields> {
 
     let mut sync_fields = fields.clone();
     let mut async_fields = fields;
     replace_item_name_in_fields(
         &mut sync_fields, 
-        &replaces_from_item_imports(item_imports_for_sync)
+        &replaces_from_variant_args(args_for_sync)
     );
     replace_item_name_in_fields(
         &mut async_fields, 
-        &replaces_from_item_imports(item_imports_for_async)
+        &replaces_from_variant_args(args_for_async)
     );
+    select_variant_attrs_in_fields(&mut sync_fields, false)?;
+    select_variant_attrs_in_fields(&mut async_fields, true)?;
 
     Ok(SyncAsyncFields { sync_fields, async_fields })
-}
\ No newline at end of file
+}
+
+fn select_variant_attrs_in_fields(fields: &mut Fields, asyncness: bool) -> Result<()> {
+    for field in fields.iter_mut() {
+        field.attrs = select_variant_attrs(std::mem::take(&mut field.attrs), asyncness)?;
+    }
+    Ok(())
+}
@@ -1,6 +1,6 @@
 use super::replace_item_name::*;
 use syn::{Generics, Result};
-use crate::ItemImport;
+use crate::VariantArgs;
 
 
 pub struct SyncAsyncGenerics {
@@ -10,19 +10,19 @@ pub struct SyncAsyncGenerics {
 
 pub fn map_generics(
     generics: Generics,
-    item_imports_for_sync: &[ItemImport],
-    item_imports_for_async: &[ItemImport]
+    args_for_sync: &VariantArgs,
+    args_for_async: &VariantArgs
 ) -> Result<SyncAsyncGenerics> {
 
     let mut sync_generics = generics.clone();
     let mut async_generics = generics;
     replace_item_name_in_generics(
         &mut sync_generics, 
-        &replaces_from_item_imports(&item_imports_for_sync)
+        &replaces_from_variant_args(args_for_sync)
     );
     replace_item_name_in_generics(
         &mut async_generics, 
-        &replaces_from_item_imports(&item_imports_for_async)
+        &replaces_from_variant_args(args_for_async)
     );
         
     Ok(SyncAsyncGenerics { sync_generics, async_generics })
@@ -1,119 +1,120 @@
-use std::{collections::VecDeque, fmt::Display};
-use syn::{spanned::Spanned, Attribute, Block, Item, Result, Signature, Stmt};
-use crate::{item_uses_from_item_imports, map::{map_items::map_fn::{get_attrs::get_attrs_mut_from_stmt, remove_await::remove_await_from_stmt}, replace_item_name_in_signature, replaces_from_item_imports}, ItemImport};
-use crate::map::replace_item_name_in_doc;
+use std::{borrow::Cow, fmt::Display};
+use syn::{spanned::Spanned, Attribute, Block, LitStr, Meta, Result, Signature, Stmt};
+use crate::{
+    format_ident_with_template, items_from_variant_args, FnMarkerArgs, NameCase, FnArgs, ContainerContext, FnMarker, 
+    PathWithoutArgs, VariantArgs,
+    map::{
+        replace_doc_links, replace_item_name_in_block, replace_item_name_in_signature, replaces_from_path_rewrites, 
+        replaces_from_variant_args, select_variant_attrs,
+        map_items::map_fn::{
+            infer_marker::infer_marker, insert_await::insert_await_in_block, remove_await::remove_await_from_block, 
+            remove_other_variant::remove_other_variant_from_block, rename_calls::rename_calls_in_block, 
+            rewrite_expr::rewrite_expr_in_block
+        }
+    }
+};
 
 pub fn handle_fn(
     attrs: Vec<Attribute>, 
     sig: Signature,
     block: Option<Block>,
     asyncness: bool,
-    item_imports: &[ItemImport],
+    args: &VariantArgs,
+    ctx: &ContainerContext,
 ) -> Result<(Vec<Attribute>, Signature, Option<Block>)> {
 
-    if sig.asyncness.is_some() {
-        return Err(syn::Error::new(
-            sig.asyncness.span(), 
+    // async_fn = maybe などが指定されていれば async fn を印の代わりにする
+    let async_fn_marker = match (sig.asyncness, ctx.fn_args.async_fn_marker) {
+        (None, _) => None,
+        (Some(_), Some(marker)) => Some(marker),
+        (Some(a), None) => return Err(syn::Error::new(
+            a.span(), 
             "Instead of the async keyword, use the attribute #[always_async] on the function."
-        ))
-    }
+        )),
+    };
 
-    let (sig_asyncness, attrs) = take_once_or_none_with_filter_map_from_attrs(
+    let (marker, attrs) = take_once_or_none_with_filter_map_from_attrs(
         attrs, 
-        |attr| {
-            if is_always_async_attr(&attr) {
-                Some(Some(syn::token::Async::default()))
-            }
-            else if is_always_sync_attr(&attr) {
-                Some(None)
-            }
-            else if is_maybe_async_attr(&attr) {
-                if asyncness {
-                    Some(Some(syn::token::Async::default()))
-                }
-                else {
-                    Some(None)
-                }
-            }
-            else {
-                None
-            }
-        }, 
+        marker_from_attr,
         || "Use one of the attributes #[always_async], #[always_sync], or #[maybe_async] on the function."
     )?;
 
     let mut sig = sig;
 
-    if let Some((_, sig_asyncness)) = sig_asyncness {
-        sig.asyncness = sig_asyncness;
-    }
-    else {
-        return Err(syn::Error::new(
-            sig.fn_token.span(), 
-            "Use only one of the attributes #[always_async], #[always_sync], or #[maybe_async] on the function."
-        ))
-    }
+    // 印がなければ async fn かどうかを見て、それもなければ本体から推測するか、
+    // default = ... で指定された印を使う
+    let (marker_attr, marker) = match marker {
+        Some((attr, marker)) => (Some(attr), marker),
+        None => {
+            let marker = async_fn_marker
+                .or_else(|| ctx.fn_args.infer_marker.then(|| infer_marker(block.as_ref(), &ctx.auto_awaited_fns)))
+                .or(ctx.fn_args.default_marker);
+            let Some(marker) = marker else {
+                // ネストした impl などの中の印のない関数は変換しない
+                if ctx.keep_unmarked {
+                    return Ok((select_variant_attrs(attrs, asyncness)?, sig, block));
+                }
+                return Err(syn::Error::new(
+                    sig.fn_token.span(), 
+                    "Use only one of the attributes #[always_async], #[always_sync], or #[maybe_async] on the function."
+                ))
+            };
+            (None, marker)
+        }
+    };
+    sig.asyncness = match marker {
+        FnMarker::AlwaysAsync => Some(Default::default()),
+        FnMarker::AlwaysSync => None,
+        FnMarker::MaybeAsync => asyncness.then(Default::default),
+    };
 
-    let replaces = &replaces_from_item_imports(item_imports);
-    replace_item_name_in_signature(&mut sig, replaces);
+    // #[maybe_async(use(if_sync) foo::bar)] のような関数ごとの引数はコンテナの引数より優先する。
+    // 名前の書式はコンテナ自身の名前に使われるので、関数ごとに指定されたものだけを使う
+    let (args, fn_name) = match &marker_attr {
+        Some(attr) if !matches!(attr.meta, Meta::Path(_)) => {
+            let marker_args = attr.parse_args::<FnMarkerArgs>()?;
+            let marker_args = match asyncness {
+                true => marker_args.for_async,
+                false => marker_args.for_sync,
+            };
+            let fn_name = marker_args.name.clone();
+            (Cow::Owned(marker_args.merged_with(args)), fn_name)
+        }
+        _ => (Cow::Borrowed(args), None),
+    };
+    let args = &*args;
+
+    replace_item_name_in_signature(&mut sig, &replaces_from_variant_args(args));
+    if let Some(name) = &fn_name {
+        NameCase::Snake.check_template(name)?;
+        sig.ident = format_ident_with_template(&name.value(), &sig.ident);
+    }
 
     let block = match block {
         None => None,
         Some(mut block) => {
-            let mut new_stmts = Vec::with_capacity(block.stmts.len());
-
-            for item_use in item_uses_from_item_imports(item_imports) {
-                new_stmts.push(Stmt::Item(Item::Use(item_use)));
+            remove_other_variant_from_block(&mut block, asyncness)?;
+            // .await を取り除く前に書き換える (from が .await を含むことがあるため)
+            rewrite_expr_in_block(&mut block, &args.expr_rewrites);
+            // awaited_fns は新しい名前なので、.await を付ける前に呼び出しの名前を変える
+            rename_calls_in_block(&mut block, &ctx.fn_renames);
+            if sig.asyncness.is_some() {
+                insert_await_in_block(&mut block, &ctx.awaited_fns);
+            }
+            // always_async の関数は sync バリアントでも async fn なので、.await や async ブロックを残す
+            if sig.asyncness.is_none() {
+                remove_await_from_block(&mut block);
             }
 
-            let mut buf = VecDeque::from_iter(block.stmts.into_iter());
-            while let Some(mut stmt) = buf.pop_front() {
-                if !asyncness {
-                    remove_await_from_stmt(&mut stmt);
-                }
+            // 本体の名前は注入する use で解決されるので、パスの書き換えだけを行う
+            replace_item_name_in_block(&mut block, &replaces_from_path_rewrites(&args.path_rewrites));
 
-                if let Some(attrs) = get_attrs_mut_from_stmt(&mut stmt) {
-
-                    #[derive(PartialEq, Eq)]
-                    enum Target {
-                        IfSync,
-                        IfAsync,
-                    }
-
-                    let (target, rest_attrs) = take_once_or_none_with_filter_map_from_attrs(
-                        attrs.clone(), 
-                        |attr| {
-                            if is_if_async_attr(attr) {
-                                Some(Target::IfAsync)
-                            }
-                            else if is_if_sync_attr(attr) {
-                                Some(Target::IfSync)
-                            }
-                            else {
-                                None
-                            }
-                        },
-                        || "expected only one of: `#[if_async]`, `#[if_sync]`"
-                    )?;
-
-                    *attrs = rest_attrs;
-
-                    if let Some((_, target)) = target {
-                        if asyncness && target == Target::IfAsync {
-                            new_stmts.push(stmt)
-                        }
-                        else if !asyncness && target == Target::IfSync {
-                            new_stmts.push(stmt)
-                        }
-                    }
-                    else {
-                        new_stmts.push(stmt);
-                    }
-                }
-                else {
-                    new_stmts.push(stmt);
-                }
+            let items = items_from_variant_args(args);
+            let mut new_stmts = Vec::with_capacity(items.len() + block.stmts.len());
+            for item in items {
+                new_stmts.push(Stmt::Item(item));
             }
+            new_stmts.append(&mut block.stmts);
 
             block.stmts = new_stmts;
             Some(block)
@@ -121,14 +122,61 @@ pub fn handle_fn(
     };
     
     let attrs = {
-        let mut attrs = attrs;
-        replace_item_name_in_doc(&mut attrs, replaces);
+        let mut attrs = select_variant_attrs(attrs, asyncness)?;
+        replace_doc_links(&mut attrs, args, ctx);
         attrs
     };
 
     Ok((attrs, sig, block))
 }
 
+fn marker_from_attr(attr: &Attribute) -> Option<FnMarker> {
+    if is_always_async_attr(attr) {
+        Some(FnMarker::AlwaysAsync)
+    }
+    else if is_always_sync_attr(attr) {
+        Some(FnMarker::AlwaysSync)
+    }
+    else if is_maybe_async_attr(attr) {
+        Some(FnMarker::MaybeAsync)
+    }
+    else {
+        None
+    }
+}
+
+/// 属性を取り除かずに関数の印を調べる。印が決まらない場合は None。
+/// auto_awaited_fns は infer で使う、.await を書かずに呼ばれる関数のパス
+pub fn peek_fn_marker(
+    attrs: &[Attribute], 
+    sig: &Signature, 
+    block: Option<&Block>, 
+    fn_args: &FnArgs,
+    auto_awaited_fns: &[PathWithoutArgs],
+) -> Option<FnMarker> {
+
+    attrs
+        .iter()
+        .find_map(marker_from_attr)
+        .or(sig.asyncness.and(fn_args.async_fn_marker))
+        .or_else(|| fn_args.infer_marker.then(|| infer_marker(block, auto_awaited_fns)))
+        .or(fn_args.default_marker)
+}
+
+/// 属性を取り除かずに、印の引数で指定された (sync, async) の名前の書式を調べる。
+/// 引数が不正な場合は handle_fn でエラーにするので、ここでは None とする
+pub fn peek_fn_name_templates(attrs: &[Attribute]) -> (Option<LitStr>, Option<LitStr>) {
+    let args = attrs
+        .iter()
+        .find(|attr| marker_from_attr(attr).is_some() && !matches!(attr.meta, Meta::Path(_)))
+        .and_then(|attr| attr.parse_args::<FnMarkerArgs>().ok());
+
+    match args {
+        Some(args) => (args.for_sync.name, args.for_async.name),
+        None => (None, None),
+    }
+}
+
 fn is_always_sync_attr(attr: &Attribute) -> bool {
     attr.path().is_ident("always_sync")
 }
@@ -141,11 +189,11 @@ fn is_maybe_async_attr(attr: &Attribute) -> bool {
     attr.path().is_ident("maybe_async")
 }
 
-fn is_if_async_attr(attr: &Attribute) -> bool {
+pub fn is_if_async_attr(attr: &Attribute) -> bool {
     attr.path().is_ident("if_async")
 }
 
-fn is_if_sync_attr(attr: &Attribute) -> bool {
+pub fn is_if_sync_attr(attr: &Attribute) -> bool {
     attr.path().is_ident("if_sync")
 }
 
@@ -167,11 +215,14 @@ fn _take_once_or_none_from_attrs<E: Display>(
     ).map(|(t, r)| (t.map(|(a, _)| a), r))
 }
 
-fn take_once_or_none_with_filter_map_from_attrs<E: Display, T>(
+/// (取り出した属性とその値, 残りの属性)
+pub type TakenAttrs<T> = (Option<(Attribute, T)>, Vec<Attribute>);
+
+pub fn take_once_or_none_with_filter_map_from_attrs<E: Display, T>(
     attrs: Vec<Attribute>, 
     filter_map: impl Fn(&Attribute) -> Option<T>,
     err_msg: impl FnOnce() -> E
-) -> Result<(Option<(Attribute, T)>, Vec<Attribute>)> {
+) -> Result<TakenAttrs<T>> {
 
     let mut target = None;
     let mut rest = Vec::with_capacity(attrs.len());
@@ -0,0 +1,149 @@
+use std::iter::Peekable;
+use proc_macro2::{token_stream, Delimiter, Ident, TokenStream, TokenTree};
+use quote::ToTokens;
+use syn::Block;
+use crate::{FnMarker, PathWithoutArgs};
+
+
+/// 本体に .await や #[if_async] / #[if_sync] などがあれば maybe_async、なければ always_sync とみなす。
+/// auto_await の場合は .await を書かずに呼ばれるので、async_fns のパスの関数の呼び出しも maybe_async の根拠にする。
+/// 本体のない関数は always_sync とする。
+pub fn infer_marker(block: Option<&Block>, async_fns: &[PathWithoutArgs]) -> FnMarker {
+    match block {
+        Some(block) if has_variant_dependent_tokens(block.to_token_stream(), async_fns) => FnMarker::MaybeAsync,
+        _ => FnMarker::AlwaysSync,
+    }
+}
+
+/// マクロの中も見るためにトークン列で探す
+fn has_variant_dependent_tokens(tokens: TokenStream, async_fns: &[PathWithoutArgs]) -> bool {
+    let mut iter = tokens.into_iter().peekable();
+    let mut prev = Vec::new();
+
+    while let Some(tt) = iter.next() {
+        match &tt {
+            // async ブロックや async クロージャの中の .await はこの関数のバリアントに関係しない
+            TokenTree::Ident(i) if i == "async" => {
+                skip_async(&mut iter);
+            },
+            // .await
+            TokenTree::Punct(p) if p.as_char() == '.' => {
+                if matches!(iter.peek(), Some(TokenTree::Ident(i)) if i == "await") {
+                    return true;
+                }
+            },
+            // #[if_async] / #[if_sync]
+            TokenTree::Punct(p) if p.as_char() == '#' => {
+                if let Some(TokenTree::Group(g)) = iter.peek() {
+                    let is_if_attr = g.delimiter() == Delimiter::Bracket && matches!(
+                        g.stream().into_iter().next(),
+                        Some(TokenTree::Ident(i)) if i == "if_async" || i == "if_sync"
+                    );
+                    if is_if_attr {
+                        return true;
+                    }
+                }
+            },
+            // is_async!() / select_variant!()
+            TokenTree::Ident(i) if i == "is_async" || i == "select_variant" => {
+                if matches!(iter.peek(), Some(TokenTree::Punct(p)) if p.as_char() == '!') {
+                    return true;
+                }
+            },
+            // auto_await で .await が付く呼び出し
+            TokenTree::Ident(i) if async_fns.iter().any(|f| f.last_ident() == Some(i)) => {
+                let is_call = matches!(iter.peek(), Some(TokenTree::Group(g)) if g.delimiter() == Delimiter::Parenthesis);
+                if is_call && is_auto_awaited_call(&prev, i, async_fns) {
+                    return true;
+                }
+            },
+            TokenTree::Group(g) if has_variant_dependent_tokens(g.stream(), async_fns) => {
+                return true;
+            },
+            _ => (),
+        }
+        prev.push(tt);
+    }
+
+    false
+}
+
+/// async の後の async ブロック、async クロージャ、async fn を読み飛ばす。
+/// クロージャの本体が {} でなければ、次の , か ; までを本体とみなす
+fn skip_async(iter: &mut Peekable<token_stream::IntoIter>) {
+    let is_punct = |tt: Option<&TokenTree>, c: char| matches!(tt, Some(TokenTree::Punct(p)) if p.as_char() == c);
+
+    if matches!(iter.peek(), Some(TokenTree::Ident(i)) if i == "move") {
+        iter.next();
+    }
+
+    // async {} や async fn f() {} は最初の {} まで
+    if !is_punct(iter.peek(), '|') {
+        skip_to_brace(iter);
+        return;
+    }
+
+    // |x| や || の引数
+    iter.next();
+    for tt in iter.by_ref() {
+        if is_punct(Some(&tt), '|') {
+            break;
+        }
+    }
+
+    // 戻り値の型がある場合は本体は {} になる
+    if is_punct(iter.peek(), '-') {
+        skip_to_brace(iter);
+        return;
+    }
+    if matches!(iter.peek(), Some(TokenTree::Group(g)) if g.delimiter() == Delimiter::Brace) {
+        iter.next();
+        return;
+    }
+
+    while let Some(tt) = iter.peek() {
+        if is_punct(Some(tt), ',') || is_punct(Some(tt), ';') {
+            break;
+        }
+        iter.next();
+    }
+}
+
+fn skip_to_brace(iter: &mut Peekable<token_stream::IntoIter>) {
+    for tt in iter.by_ref() {
+        if let TokenTree::Group(g) = tt {
+            if g.delimiter() == Delimiter::Brace {
+                break;
+            }
+        }
+    }
+}
+
+/// 関数名 ident の前のトークンと合わせて、async_fns のパスの関数の呼び出しになっているか。
+/// insert_await と同じく、パスは async_fns のものと一致する場合だけ (impl の中の f() は含まない)、
+/// self.f() は Self::f がある場合だけ対象にする。fn f() の定義は含まない
+fn is_auto_awaited_call(prev: &[TokenTree], ident: &Ident, async_fns: &[PathWithoutArgs]) -> bool {
+    let back = |k: usize| prev.len().checked_sub(k).map(|i| &prev[i]);
+    let is_punct = |k: usize, c: char| matches!(back(k), Some(TokenTree::Punct(p)) if p.as_char() == c);
+    let is_ident = |k: usize, s: &str| matches!(back(k), Some(TokenTree::Ident(i)) if i == s);
+
+    if is_punct(1, '.') {
+        return is_ident(2, "self") && async_fns.iter().any(|f| f.is_self_item(ident));
+    }
+    if is_ident(1, "fn") {
+        return false;
+    }
+
+    // Foo::bar::f の Foo, bar を後ろから集める
+    let mut segments = vec![ident.clone()];
+    let mut k = 1;
+    while is_punct(k, ':') && is_punct(k + 1, ':') {
+        let Some(TokenTree::Ident(seg)) = back(k + 2) else {
+            break;
+        };
+        segments.insert(0, seg.clone());
+        k += 3;
+    }
+
+    async_fns.iter().any(|f| f.segments.iter().eq(segments.iter()))
+}
@@ -0,0 +1,236 @@
+use syn::{Block, Expr, ExprAwait, Local, Macro, Stmt};
+use crate::{map_macro_exprs, PathWithoutArgs};
+
+
+/// names のパスの関数の呼び出しに .await を付ける。
+/// モジュールの関数は f(..)、impl やトレイトの関数は Self::f(..), Foo::f(..), self.f(..) の形で呼ばれる。
+/// すでに .await が付いているものはそのままにする。
+pub fn insert_await_in_block(block: &mut Block, names: &[PathWithoutArgs]) {
+    if names.is_empty() {
+        return;
+    }
+
+    for stmt in &mut block.stmts {
+        insert_await_in_stmt(stmt, names);
+    }
+}
+
+pub fn insert_await_in_stmt(stmt: &mut Stmt, names: &[PathWithoutArgs]) {
+    match stmt {
+        Stmt::Local(i) => insert_await_in_local(i, names),
+        Stmt::Expr(i, _) => insert_await(i, names),
+        Stmt::Macro(i) => insert_await_in_macro(&mut i.mac, names),
+        Stmt::Item(_) => (),
+    }
+}
+
+pub fn insert_await_in_local(local: &mut Local, names: &[PathWithoutArgs]) {
+    if let Some(init) = &mut local.init {
+        insert_await(&mut init.expr, names);
+        if let Some((_, diverge)) = &mut init.diverge {
+            insert_await(diverge, names);
+        }
+    }
+}
+
+pub fn insert_await(expr: &mut Expr, names: &[PathWithoutArgs]) {
+    insert_await_in_children(expr, names);
+
+    if is_target_call(expr, names) {
+        let d = Expr::Verbatim(Default::default());
+        let call = std::mem::replace(expr, d);
+        *expr = Expr::Await(ExprAwait {
+            attrs: Vec::new(),
+            base: Box::new(call),
+            dot_token: Default::default(),
+            await_token: Default::default(),
+        });
+    }
+}
+
+fn insert_await_in_children(expr: &mut Expr, names: &[PathWithoutArgs]) {
+    match expr {
+        Expr::Array(i) => {
+            for e in &mut i.elems {
+                insert_await(e, names);
+            }
+        },
+        Expr::Assign(i) => {
+            insert_await(&mut i.left, names);
+            insert_await(&mut i.right, names);
+        },
+        Expr::Async(i) => {
+            insert_await_in_block(&mut i.block, names);
+        },
+        Expr::Await(i) => {
+            // f().await の f() には付けない
+            match is_target_call(&i.base, names) {
+                true => insert_await_in_children(&mut i.base, names),
+                false => insert_await(&mut i.base, names),
+            }
+        },
+        Expr::Binary(i) => {
+            insert_await(&mut i.left, names);
+            insert_await(&mut i.right, names);
+        },
+        Expr::Block(i) => {
+            insert_await_in_block(&mut i.block, names);
+        },
+        Expr::Break(i) => {
+            if let Some(e) = &mut i.expr {
+                insert_await(e, names);
+            }
+        },
+        Expr::Call(i) => {
+            insert_await(&mut i.func, names);
+            for e in &mut i.args {
+                insert_await(e, names);
+            }
+        },
+        Expr::Cast(i) => {
+            insert_await(&mut i.expr, names);
+        },
+        // async でないクロージャの中では .await を使えない
+        Expr::Closure(i) if i.asyncness.is_some() => {
+            insert_await(&mut i.body, names);
+        },
+        Expr::Closure(_) => (),
+        Expr::Const(_) => (),
+        Expr::Field(i) => {
+            insert_await(&mut i.base, names);
+        },
+        Expr::ForLoop(i) => {
+            insert_await(&mut i.expr, names);
+            insert_await_in_block(&mut i.body, names);
+        },
+        Expr::Group(i) => {
+            insert_await(&mut i.expr, names);
+        },
+        Expr::If(i) => {
+            insert_await(&mut i.cond, names);
+            insert_await_in_block(&mut i.then_branch, names);
+            if let Some((_, e)) = &mut i.else_branch {
+                insert_await(e, names);
+            }
+        },
+        Expr::Index(i) => {
+            insert_await(&mut i.expr, names);
+            insert_await(&mut i.index, names);
+        },
+        Expr::Let(i) => {
+            insert_await(&mut i.expr, names);
+        },
+        Expr::Loop(i) => {
+            insert_await_in_block(&mut i.body, names);
+        },
+        Expr::Match(i) => {
+            insert_await(&mut i.expr, names);
+            for a in &mut i.arms {
+                insert_await(&mut a.body, names);
+                if let Some((_, g)) = &mut a.guard {
+                    insert_await(g, names);
+                }
+            }
+        },
+        Expr::MethodCall(i) => {
+            insert_await(&mut i.receiver, names);
+            for a in &mut i.args {
+                insert_await(a, names);
+            }
+        },
+        Expr::Paren(i) => {
+            insert_await(&mut i.expr, names);
+        },
+        Expr::Range(i) => {
+            if let Some(s) = &mut i.start {
+                insert_await(s, names);
+            }
+            if let Some(e) = &mut i.end {
+                insert_await(e, names);
+            }
+        },
+        Expr::RawAddr(i) => {
+            insert_await(&mut i.expr, names);
+        },
+        Expr::Reference(i) => {
+            insert_await(&mut i.expr, names);
+        },
+        Expr::Repeat(i) => {
+            insert_await(&mut i.expr, names);
+        },
+        Expr::Return(i) => {
+            if let Some(e) = &mut i.expr {
+                insert_await(e, names);
+            }
+        },
+        Expr::Struct(i) => {
+            for f in &mut i.fields {
+                insert_await(&mut f.expr, names);
+            }
+            if let Some(r) = &mut i.rest {
+                insert_await(r, names);
+            }
+        },
+        Expr::Try(i) => {
+            insert_await(&mut i.expr, names);
+        },
+        Expr::TryBlock(i) => {
+            insert_await_in_block(&mut i.block, names);
+        },
+        Expr::Tuple(i) => {
+            for e in &mut i.elems {
+                insert_await(e, names);
+            }
+        },
+        Expr::Unary(i) => {
+            insert_await(&mut i.expr, names);
+        },
+        Expr::Unsafe(i) => {
+            insert_await_in_block(&mut i.block, names);
+        },
+        Expr::While(i) => {
+            insert_await(&mut i.cond, names);
+            insert_await_in_block(&mut i.body, names);
+        },
+        Expr::Yield(i) => {
+            if let Some(e) = &mut i.expr {
+                insert_await(e, names);
+            }
+        },
+        Expr::Verbatim(_) => (),
+        Expr::Path(_) => (),
+        Expr::Macro(i) => {
+            insert_await_in_macro(&mut i.mac, names);
+        },
+        Expr::Lit(_) => (),
+        Expr::Infer(_) => (),
+        Expr::Continue(_) => (),
+        _ => (),
+    }
+}
+
+/// assert!(f() > 0) のように、マクロの引数の中の呼び出しにも付ける。
+/// 引数を式の並びとして読めないマクロはそのままにする
+fn insert_await_in_macro(mac: &mut Macro, names: &[PathWithoutArgs]) {
+    let _ = map_macro_exprs(mac, |expr| {
+        insert_await(expr, names);
+        Ok(())
+    });
+}
+
+/// names のパスの関数の呼び出しか。self.f(..) は Self::f が names にある場合に限る
+fn is_target_call(expr: &Expr, names: &[PathWithoutArgs]) -> bool {
+    match expr {
+        Expr::Call(e) => {
+            let Expr::Path(p) = &*e.func else {
+                return false;
+            };
+            p.qself.is_none() && names.iter().any(|n| *n == p.path)
+        },
+        Expr::MethodCall(e) => {
+            let is_self = matches!(&*e.receiver, Expr::Path(p) if p.path.is_ident("self"));
+            is_self && names.iter().any(|n| n.is_self_item(&e.method))
+        },
+        _ => false,
+    }
+}
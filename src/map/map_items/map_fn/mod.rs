@@ -1,16 +1,23 @@
 mod get_attrs;
 mod handle_fn;
+mod infer_marker;
+mod insert_await;
 mod remove_await;
+mod remove_other_variant;
+mod rename_calls;
+mod rewrite_expr;
 
+pub use handle_fn::{peek_fn_marker, peek_fn_name_templates};
 use handle_fn::*;
-use syn::{Block, ImplItem, ImplItemFn, Item, ItemFn, Result};
-use crate::ItemImport;
+use syn::{Block, ImplItem, ImplItemFn, Item, ItemFn, Result, TraitItem, TraitItemFn};
+use crate::{ContainerContext, VariantArgs};
 
 
 pub fn map_impl_fn(
     item: ImplItemFn, 
     asyncness: bool,
-    item_imports: &[ItemImport],
+    args: &VariantArgs,
+    ctx: &ContainerContext,
 ) -> Result<ImplItem> {
 
     let (attrs, sig, block) = handle_fn(
@@ -18,7 +25,8 @@ pub fn map_impl_fn(
         item.sig,
         Some(item.block), 
         asyncness,
-        item_imports,
+        args,
+        ctx,
     )?;
 
     let block = block.unwrap();
@@ -28,7 +36,8 @@ pub fn map_impl_fn(
 pub fn map_mod_fn(
     item: ItemFn, 
     asyncness: bool,
-    item_imports: &[ItemImport],
+    args: &VariantArgs,
+    ctx: &ContainerContext,
 ) -> Result<Item> {
 
     let (attrs, sig, block) = handle_fn(
@@ -36,9 +45,29 @@ pub fn map_mod_fn(
         item.sig, 
         Some(Block::clone(&item.block)), 
         asyncness,
-        item_imports,
+        args,
+        ctx,
     )?;
 
     let block = Box::new(block.unwrap());
     Ok(Item::Fn(ItemFn { attrs, sig, block, ..item }))
+}
+
+pub fn map_trait_fn(
+    item: TraitItemFn, 
+    asyncness: bool,
+    args: &VariantArgs,
+    ctx: &ContainerContext,
+) -> Result<TraitItem> {
+
+    let (attrs, sig, default) = handle_fn(
+        item.attrs, 
+        item.sig, 
+        item.default, 
+        asyncness,
+        args,
+        ctx,
+    )?;
+
+    Ok(TraitItem::Fn(TraitItemFn { attrs, sig, default, ..item }))
 }
\ No newline at end of file
@@ -1,12 +1,14 @@
-use syn::{Block, Expr, Local, Stmt};
-use crate::map::map_items::map_fn::get_attrs::get_attrs_mut_from_expr;
+use proc_macro2::{Group, TokenStream, TokenTree};
+use quote::ToTokens;
+use syn::{parse_quote, Block, Expr, Local, Macro, Stmt};
+use crate::{map::map_items::map_fn::get_attrs::get_attrs_mut_from_expr, map_macro_exprs};
 
 
 pub fn remove_await_from_stmt(stmt: &mut Stmt) {
     match stmt {
         Stmt::Local(i) => remove_await_from_local(i),
         Stmt::Expr(i, _) => remove_await_from_expr(i),
-        Stmt::Macro(_) => (),
+        Stmt::Macro(i) => remove_await_from_macro(&mut i.mac),
         Stmt::Item(_) => (),
     }
 }
@@ -28,8 +30,8 @@ pub fn remove_await_from_expr(expr: &mut Expr) {
             remove_await_from_expr(&mut i.left);
             remove_await_from_expr(&mut i.right);
         },
-        Expr::Async(i) => {
-            remove_await_from_block(&mut i.block);
+        Expr::Async(_) => {
+            remove_await_from_expr_async(expr);
         },
         Expr::Await(_) => {
             remove_await_from_expr_await(expr);
@@ -56,6 +58,8 @@ pub fn remove_await_from_expr(expr: &mut Expr) {
             remove_await_from_expr(&mut i.expr);
         },
         Expr::Closure(i) => {
+            // async クロージャは普通のクロージャにする
+            i.asyncness = None;
             remove_await_from_expr(&mut i.body);
         },
         Expr::Const(i) => {
@@ -165,7 +169,7 @@ pub fn remove_await_from_expr(expr: &mut Expr) {
         },
         Expr::Verbatim(_) => (),
         Expr::Path(_) => (),
-        Expr::Macro(_) => (),
+        Expr::Macro(i) => remove_await_from_macro(&mut i.mac),
         Expr::Lit(_) => (),
         Expr::Infer(_) => (),
         Expr::Continue(_) => (),
@@ -190,4 +194,76 @@ pub fn remove_await_from_expr_await(expr: &mut Expr) {
         *expr = base_expr;
         remove_await_from_expr(expr);
     }
+}
+
+/// async ブロックを即時評価されるブロックにする。
+/// ブロック内で return や ? が使われている場合は、それらの効果がブロックの外に漏れないように
+/// 即時実行されるクロージャにする。
+pub fn remove_await_from_expr_async(expr: &mut Expr) {
+    if let Expr::Async(async_expr) = expr {
+        remove_await_from_block(&mut async_expr.block);
+
+        let attrs = std::mem::take(&mut async_expr.attrs);
+        let capture = async_expr.capture;
+        let block = std::mem::replace(&mut async_expr.block, Block { 
+            brace_token: Default::default(), 
+            stmts: Vec::new() 
+        });
+
+        *expr = match contains_return_or_try(block.to_token_stream()) {
+            true => parse_quote! { #(#attrs)* (#capture || #block)() },
+            false => parse_quote! { #(#attrs)* #block },
+        };
+    }
+}
+
+/// トークン列に return または ? が含まれるか。
+/// 入れ子のクロージャ内のものも含むが、その場合もクロージャにするだけなので問題ない。
+fn contains_return_or_try(tokens: TokenStream) -> bool {
+    tokens.into_iter().any(|tt| match tt {
+        TokenTree::Ident(i) => i == "return",
+        TokenTree::Punct(p) => p.as_char() == '?',
+        TokenTree::Group(g) => contains_return_or_try(g.stream()),
+        TokenTree::Literal(_) => false,
+    })
+}
+
+/// マクロの中身をカンマ区切りの式として解釈できればその式から .await を取り除き、
+/// できなければトークン列から `. await` を直接取り除く
+pub fn remove_await_from_macro(mac: &mut Macro) {
+    let parsed = map_macro_exprs(mac, |expr| {
+        remove_await_from_expr(expr);
+        Ok(())
+    });
+    if !matches!(parsed, Ok(true)) {
+        let tokens = std::mem::take(&mut mac.tokens);
+        mac.tokens = remove_await_from_tokens(tokens);
+    }
+}
+
+fn remove_await_from_tokens(tokens: TokenStream) -> TokenStream {
+    let mut buf = Vec::new();
+    let mut iter = tokens.into_iter().peekable();
+
+    while let Some(tt) = iter.next() {
+        match tt {
+            TokenTree::Punct(p) if p.as_char() == '.' => {
+                if let Some(TokenTree::Ident(i)) = iter.peek() {
+                    if i == "await" {
+                        iter.next();
+                        continue;
+                    }
+                }
+                buf.push(TokenTree::Punct(p));
+            },
+            TokenTree::Group(g) => {
+                let mut new_group = Group::new(g.delimiter(), remove_await_from_tokens(g.stream()));
+                new_group.set_span(g.span());
+                buf.push(TokenTree::Group(new_group));
+            },
+            tt => buf.push(tt),
+        }
+    }
+
+    buf.into_iter().collect()
 }
\ No newline at end of file
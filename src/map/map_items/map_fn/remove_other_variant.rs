@@ -0,0 +1,356 @@
+use syn::{
+    punctuated::Punctuated, spanned::Spanned, Arm, Attribute, Block, Error, Expr, ExprLit, ExprParen, 
+    FieldValue, Lit, LitBool, Local, Macro, Result, Stmt, Token
+};
+use crate::{
+    map_macro_exprs,
+    map::map_items::map_fn::{
+        get_attrs::{get_attrs_mut_from_expr, get_attrs_mut_from_stmt},
+        handle_fn::{is_if_async_attr, is_if_sync_attr, take_once_or_none_with_filter_map_from_attrs}
+    }
+};
+
+
+/// #[if_sync] / #[if_async] が付いた文・match のアーム・構造体式のフィールドのうち、
+/// 他方のバリアント向けのものを取り除く。
+pub fn remove_other_variant_from_block(block: &mut Block, asyncness: bool) -> Result<()> {
+    let stmts = std::mem::take(&mut block.stmts);
+
+    for mut stmt in stmts {
+        let is_target = match get_attrs_mut_from_stmt(&mut stmt) {
+            Some(attrs) => is_target_variant(attrs, asyncness)?,
+            None => true,
+        };
+
+        if is_target {
+            remove_other_variant_from_stmt(&mut stmt, asyncness)?;
+            block.stmts.push(stmt);
+        }
+    }
+
+    // select_variant!{..} のような ; のないマクロの文を式にした場合、
+    // 途中の文が末尾の式にならないように ; を付ける
+    if let Some((_, stmts)) = block.stmts.split_last_mut() {
+        for stmt in stmts {
+            if let Stmt::Expr(Expr::Paren(_) | Expr::Lit(_), semi @ None) = stmt {
+                *semi = Some(Default::default());
+            }
+        }
+    }
+
+    Ok(())
+}
+
+pub fn remove_other_variant_from_stmt(stmt: &mut Stmt, asyncness: bool) -> Result<()> {
+    match stmt {
+        Stmt::Local(i) => remove_other_variant_from_local(i, asyncness),
+        Stmt::Expr(i, _) => remove_other_variant_from_expr(i, asyncness),
+        Stmt::Macro(i) => {
+            if !is_variant_selection_macro(&i.mac) {
+                return remove_other_variant_from_macro(&mut i.mac, asyncness);
+            }
+
+            let mut expr = select_variant_from_macro(&i.mac, asyncness)?;
+            if let Some(attrs) = get_attrs_mut_from_expr(&mut expr) {
+                attrs.append(&mut i.attrs);
+            }
+            *stmt = Stmt::Expr(expr, i.semi_token);
+            Ok(())
+        },
+        Stmt::Item(_) => Ok(()),
+    }
+}
+
+pub fn remove_other_variant_from_local(local: &mut Local, asyncness: bool) -> Result<()> {
+    if let Some(init) = &mut local.init {
+        remove_other_variant_from_expr(&mut init.expr, asyncness)?;
+        if let Some((_, diverge)) = &mut init.diverge {
+            remove_other_variant_from_expr(diverge, asyncness)?;
+        }
+    }
+    Ok(())
+}
+
+pub fn remove_other_variant_from_expr(expr: &mut Expr, asyncness: bool) -> Result<()> {
+    match expr {
+        Expr::Array(i) => {
+            for e in &mut i.elems {
+                remove_other_variant_from_expr(e, asyncness)?;
+            }
+        },
+        Expr::Assign(i) => {
+            remove_other_variant_from_expr(&mut i.left, asyncness)?;
+            remove_other_variant_from_expr(&mut i.right, asyncness)?;
+        },
+        Expr::Async(i) => {
+            remove_other_variant_from_block(&mut i.block, asyncness)?;
+        },
+        Expr::Await(i) => {
+            remove_other_variant_from_expr(&mut i.base, asyncness)?;
+        },
+        Expr::Binary(i) => {
+            remove_other_variant_from_expr(&mut i.left, asyncness)?;
+            remove_other_variant_from_expr(&mut i.right, asyncness)?;
+        },
+        Expr::Block(i) => {
+            remove_other_variant_from_block(&mut i.block, asyncness)?;
+        },
+        Expr::Break(i) => {
+            if let Some(e) = &mut i.expr {
+                remove_other_variant_from_expr(e, asyncness)?;
+            }
+        },
+        Expr::Call(i) => {
+            remove_other_variant_from_expr(&mut i.func, asyncness)?;
+            for e in &mut i.args {
+                remove_other_variant_from_expr(e, asyncness)?;
+            }
+        },
+        Expr::Cast(i) => {
+            remove_other_variant_from_expr(&mut i.expr, asyncness)?;
+        },
+        Expr::Closure(i) => {
+            remove_other_variant_from_expr(&mut i.body, asyncness)?;
+        },
+        Expr::Const(i) => {
+            remove_other_variant_from_block(&mut i.block, asyncness)?;
+        },
+        Expr::Field(i) => {
+            remove_other_variant_from_expr(&mut i.base, asyncness)?;
+        },
+        Expr::ForLoop(i) => {
+            remove_other_variant_from_expr(&mut i.expr, asyncness)?;
+            remove_other_variant_from_block(&mut i.body, asyncness)?;
+        },
+        Expr::Group(i) => {
+            remove_other_variant_from_expr(&mut i.expr, asyncness)?;
+        },
+        Expr::If(i) => {
+            remove_other_variant_from_block(&mut i.then_branch, asyncness)?;
+            remove_other_variant_from_expr(&mut i.cond, asyncness)?;
+            if let Some((_, e)) = &mut i.else_branch {
+                remove_other_variant_from_expr(e, asyncness)?;
+            }
+        },
+        Expr::Index(i) => {
+            remove_other_variant_from_expr(&mut i.expr, asyncness)?;
+            remove_other_variant_from_expr(&mut i.index, asyncness)?;
+        },
+        Expr::Let(i) => {
+            remove_other_variant_from_expr(&mut i.expr, asyncness)?;
+        },
+        Expr::Loop(i) => {
+            remove_other_variant_from_block(&mut i.body, asyncness)?;
+        },
+        Expr::Match(i) => {
+            remove_other_variant_from_expr(&mut i.expr, asyncness)?;
+            remove_other_variant_from_arms(&mut i.arms, asyncness)?;
+        },
+        Expr::MethodCall(i) => {
+            remove_other_variant_from_expr(&mut i.receiver, asyncness)?;
+            for a in &mut i.args {
+                remove_other_variant_from_expr(a, asyncness)?;
+            }
+        },
+        Expr::Paren(i) => {
+            remove_other_variant_from_expr(&mut i.expr, asyncness)?;
+        },
+        Expr::Range(i) => {
+            if let Some(s) = &mut i.start {
+                remove_other_variant_from_expr(s, asyncness)?;
+            }
+            if let Some(e) = &mut i.end {
+                remove_other_variant_from_expr(e, asyncness)?;
+            }
+        },
+        Expr::RawAddr(i) => {
+            remove_other_variant_from_expr(&mut i.expr, asyncness)?;
+        },
+        Expr::Reference(i) => {
+            remove_other_variant_from_expr(&mut i.expr, asyncness)?;
+        },
+        Expr::Repeat(i) => {
+            remove_other_variant_from_expr(&mut i.expr, asyncness)?;
+            remove_other_variant_from_expr(&mut i.len, asyncness)?;
+        },
+        Expr::Return(i) => {
+            if let Some(e) = &mut i.expr {
+                remove_other_variant_from_expr(e, asyncness)?;
+            }
+        },
+        Expr::Struct(i) => {
+            remove_other_variant_from_field_values(&mut i.fields, asyncness)?;
+            if let Some(r) = &mut i.rest {
+                remove_other_variant_from_expr(r, asyncness)?;
+            }
+        },
+        Expr::Try(i) => {
+            remove_other_variant_from_expr(&mut i.expr, asyncness)?;
+        },
+        Expr::TryBlock(i) => {
+            remove_other_variant_from_block(&mut i.block, asyncness)?;
+        },
+        Expr::Tuple(i) => {
+            for e in &mut i.elems {
+                remove_other_variant_from_expr(e, asyncness)?;
+            }
+        },
+        Expr::Unary(i) => {
+            remove_other_variant_from_expr(&mut i.expr, asyncness)?;
+        },
+        Expr::Unsafe(i) => {
+            remove_other_variant_from_block(&mut i.block, asyncness)?;
+        },
+        Expr::While(i) => {
+            remove_other_variant_from_expr(&mut i.cond, asyncness)?;
+            remove_other_variant_from_block(&mut i.body, asyncness)?;
+        },
+        Expr::Yield(i) => {
+            if let Some(e) = &mut i.expr {
+                remove_other_variant_from_expr(e, asyncness)?;
+            }
+        },
+        Expr::Verbatim(_) => (),
+        Expr::Path(_) => (),
+        Expr::Macro(i) if is_variant_selection_macro(&i.mac) => {
+            let mut new_expr = select_variant_from_macro(&i.mac, asyncness)?;
+            if let Some(attrs) = get_attrs_mut_from_expr(&mut new_expr) {
+                attrs.append(&mut i.attrs);
+            }
+            *expr = new_expr;
+        },
+        Expr::Macro(i) => {
+            remove_other_variant_from_macro(&mut i.mac, asyncness)?;
+        },
+        Expr::Lit(_) => (),
+        Expr::Infer(_) => (),
+        Expr::Continue(_) => (),
+        _ => (),
+    }
+
+    Ok(())
+}
+
+fn remove_other_variant_from_arms(arms: &mut Vec<Arm>, asyncness: bool) -> Result<()> {
+    let old_arms = std::mem::take(arms);
+
+    for mut arm in old_arms {
+        if is_target_variant(&mut arm.attrs, asyncness)? {
+            remove_other_variant_from_expr(&mut arm.body, asyncness)?;
+            if let Some((_, g)) = &mut arm.guard {
+                remove_other_variant_from_expr(g, asyncness)?;
+            }
+            arms.push(arm);
+        }
+    }
+
+    Ok(())
+}
+
+fn remove_other_variant_from_field_values(
+    fields: &mut Punctuated<FieldValue, Token![,]>,
+    asyncness: bool
+) -> Result<()> {
+
+    let old_fields = std::mem::take(fields);
+
+    for mut field in old_fields {
+        if is_target_variant(&mut field.attrs, asyncness)? {
+            remove_other_variant_from_expr(&mut field.expr, asyncness)?;
+            fields.push(field);
+        }
+    }
+
+    Ok(())
+}
+
+/// assert!(is_async!()) のように、他のマクロの引数の中の式も処理する。
+/// 引数を式の並びとして読めないマクロはそのままにする
+fn remove_other_variant_from_macro(mac: &mut Macro, asyncness: bool) -> Result<()> {
+    map_macro_exprs(mac, |expr| remove_other_variant_from_expr(expr, asyncness))?;
+    Ok(())
+}
+
+fn is_variant_selection_macro(mac: &Macro) -> bool {
+    mac.path.is_ident("is_async") || mac.path.is_ident("select_variant")
+}
+
+/// is_async!() は bool リテラルに、select_variant!(sync_expr, async_expr) は
+/// 現在のバリアントの式に置き換える。
+/// 周囲の式との優先順位が変わらないように括弧で包む。
+fn select_variant_from_macro(mac: &Macro, asyncness: bool) -> Result<Expr> {
+    let mut expr = if mac.path.is_ident("is_async") {
+        if !mac.tokens.is_empty() {
+            return Err(Error::new(mac.tokens.span(), "`is_async!` takes no arguments"))
+        }
+        Expr::Lit(ExprLit {
+            attrs: Vec::new(),
+            lit: Lit::Bool(LitBool::new(asyncness, mac.path.span())),
+        })
+    }
+    else {
+        let args = mac.parse_body_with(Punctuated::<Expr, Token![,]>::parse_terminated)?;
+        if args.len() != 2 {
+            return Err(Error::new(
+                mac.tokens.span(),
+                "expected two expressions: `select_variant!(sync_expr, async_expr)`"
+            ))
+        }
+
+        let mut args = args.into_iter();
+        let sync_expr = args.next().unwrap();
+        let async_expr = args.next().unwrap();
+        let mut expr = match asyncness {
+            true => async_expr,
+            false => sync_expr,
+        };
+        remove_other_variant_from_expr(&mut expr, asyncness)?;
+        expr
+    };
+
+    if let Expr::Lit(_) = &expr {
+        return Ok(expr)
+    }
+
+    expr = Expr::Paren(ExprParen {
+        attrs: Vec::new(),
+        paren_token: Default::default(),
+        expr: Box::new(expr),
+    });
+    Ok(expr)
+}
+
+/// attrs から #[if_sync] / #[if_async] を取り除き、
+/// 現在のバリアントで残すべきなら true を返す
+fn is_target_variant(attrs: &mut Vec<Attribute>, asyncness: bool) -> Result<bool> {
+
+    #[derive(PartialEq, Eq)]
+    enum Target {
+        IfSync,
+        IfAsync,
+    }
+
+    let (target, rest_attrs) = take_once_or_none_with_filter_map_from_attrs(
+        std::mem::take(attrs),
+        |attr| {
+            if is_if_async_attr(attr) {
+                Some(Target::IfAsync)
+            }
+            else if is_if_sync_attr(attr) {
+                Some(Target::IfSync)
+            }
+            else {
+                None
+            }
+        },
+        || "expected only one of: `#[if_async]`, `#[if_sync]`"
+    )?;
+
+    *attrs = rest_attrs;
+
+    Ok(match target {
+        Some((_, Target::IfAsync)) => asyncness,
+        Some((_, Target::IfSync)) => !asyncness,
+        None => true,
+    })
+}
@@ -0,0 +1,232 @@
+use syn::{Block, Expr, Ident, Local, Macro, Path, Stmt};
+use crate::{map_macro_exprs, PathRewrite};
+
+
+/// sync_name などで名前が変わる関数の呼び出し (f(), Self::f(), self.f()) と
+/// Self::f, Foo::f のパスを新しい名前にする。
+/// f だけのパスはローカル変数かもしれないので、呼び出しの場合だけ書き換える
+pub fn rename_calls_in_block(block: &mut Block, renames: &[PathRewrite]) {
+    if renames.is_empty() {
+        return;
+    }
+
+    for stmt in &mut block.stmts {
+        rename_calls_in_stmt(stmt, renames);
+    }
+}
+
+pub fn rename_calls_in_stmt(stmt: &mut Stmt, renames: &[PathRewrite]) {
+    match stmt {
+        Stmt::Local(i) => rename_calls_in_local(i, renames),
+        Stmt::Expr(i, _) => rename_calls(i, renames),
+        Stmt::Macro(i) => rename_calls_in_macro(&mut i.mac, renames),
+        Stmt::Item(_) => (),
+    }
+}
+
+pub fn rename_calls_in_local(local: &mut Local, renames: &[PathRewrite]) {
+    if let Some(init) = &mut local.init {
+        rename_calls(&mut init.expr, renames);
+        if let Some((_, diverge)) = &mut init.diverge {
+            rename_calls(diverge, renames);
+        }
+    }
+}
+
+pub fn rename_calls(expr: &mut Expr, renames: &[PathRewrite]) {
+    match expr {
+        Expr::Array(i) => {
+            for e in &mut i.elems {
+                rename_calls(e, renames);
+            }
+        },
+        Expr::Assign(i) => {
+            rename_calls(&mut i.left, renames);
+            rename_calls(&mut i.right, renames);
+        },
+        Expr::Async(i) => {
+            rename_calls_in_block(&mut i.block, renames);
+        },
+        Expr::Await(i) => {
+            rename_calls(&mut i.base, renames);
+        },
+        Expr::Binary(i) => {
+            rename_calls(&mut i.left, renames);
+            rename_calls(&mut i.right, renames);
+        },
+        Expr::Block(i) => {
+            rename_calls_in_block(&mut i.block, renames);
+        },
+        Expr::Break(i) => {
+            if let Some(e) = &mut i.expr {
+                rename_calls(e, renames);
+            }
+        },
+        Expr::Call(i) => {
+            // f() の f は呼び出しの場合だけ書き換える
+            if let Expr::Path(p) = &mut *i.func {
+                if p.qself.is_none() && p.path.segments.len() == 1 {
+                    rename_path(&mut p.path, renames);
+                }
+            }
+            rename_calls(&mut i.func, renames);
+            for e in &mut i.args {
+                rename_calls(e, renames);
+            }
+        },
+        Expr::Cast(i) => {
+            rename_calls(&mut i.expr, renames);
+        },
+        Expr::Closure(i) => {
+            rename_calls(&mut i.body, renames);
+        },
+        Expr::Const(i) => {
+            rename_calls_in_block(&mut i.block, renames);
+        },
+        Expr::Field(i) => {
+            rename_calls(&mut i.base, renames);
+        },
+        Expr::ForLoop(i) => {
+            rename_calls(&mut i.expr, renames);
+            rename_calls_in_block(&mut i.body, renames);
+        },
+        Expr::Group(i) => {
+            rename_calls(&mut i.expr, renames);
+        },
+        Expr::If(i) => {
+            rename_calls(&mut i.cond, renames);
+            rename_calls_in_block(&mut i.then_branch, renames);
+            if let Some((_, e)) = &mut i.else_branch {
+                rename_calls(e, renames);
+            }
+        },
+        Expr::Index(i) => {
+            rename_calls(&mut i.expr, renames);
+            rename_calls(&mut i.index, renames);
+        },
+        Expr::Let(i) => {
+            rename_calls(&mut i.expr, renames);
+        },
+        Expr::Loop(i) => {
+            rename_calls_in_block(&mut i.body, renames);
+        },
+        Expr::Match(i) => {
+            rename_calls(&mut i.expr, renames);
+            for a in &mut i.arms {
+                rename_calls(&mut a.body, renames);
+                if let Some((_, g)) = &mut a.guard {
+                    rename_calls(g, renames);
+                }
+            }
+        },
+        Expr::MethodCall(i) => {
+            // self.f() は Self::f の書き換えに従う
+            let is_self = matches!(&*i.receiver, Expr::Path(p) if p.path.is_ident("self"));
+            if is_self {
+                let renamed = renames
+                    .iter()
+                    .find(|r| r.from.is_self_item(&i.method))
+                    .and_then(|r| r.to.last_ident());
+                if let Some(to) = renamed {
+                    i.method = Ident::new(&to.to_string(), i.method.span());
+                }
+            }
+            rename_calls(&mut i.receiver, renames);
+            for a in &mut i.args {
+                rename_calls(a, renames);
+            }
+        },
+        Expr::Paren(i) => {
+            rename_calls(&mut i.expr, renames);
+        },
+        // Self::f や Foo::f は呼び出しでなくても (map(Self::f) など) 書き換える
+        Expr::Path(i) if i.qself.is_none() && i.path.segments.len() > 1 => {
+            rename_path(&mut i.path, renames);
+        },
+        Expr::Range(i) => {
+            if let Some(s) = &mut i.start {
+                rename_calls(s, renames);
+            }
+            if let Some(e) = &mut i.end {
+                rename_calls(e, renames);
+            }
+        },
+        Expr::RawAddr(i) => {
+            rename_calls(&mut i.expr, renames);
+        },
+        Expr::Reference(i) => {
+            rename_calls(&mut i.expr, renames);
+        },
+        Expr::Repeat(i) => {
+            rename_calls(&mut i.expr, renames);
+            rename_calls(&mut i.len, renames);
+        },
+        Expr::Return(i) => {
+            if let Some(e) = &mut i.expr {
+                rename_calls(e, renames);
+            }
+        },
+        Expr::Struct(i) => {
+            for f in &mut i.fields {
+                rename_calls(&mut f.expr, renames);
+            }
+            if let Some(r) = &mut i.rest {
+                rename_calls(r, renames);
+            }
+        },
+        Expr::Try(i) => {
+            rename_calls(&mut i.expr, renames);
+        },
+        Expr::TryBlock(i) => {
+            rename_calls_in_block(&mut i.block, renames);
+        },
+        Expr::Tuple(i) => {
+            for e in &mut i.elems {
+                rename_calls(e, renames);
+            }
+        },
+        Expr::Unary(i) => {
+            rename_calls(&mut i.expr, renames);
+        },
+        Expr::Unsafe(i) => {
+            rename_calls_in_block(&mut i.block, renames);
+        },
+        Expr::While(i) => {
+            rename_calls(&mut i.cond, renames);
+            rename_calls_in_block(&mut i.body, renames);
+        },
+        Expr::Yield(i) => {
+            if let Some(e) = &mut i.expr {
+                rename_calls(e, renames);
+            }
+        },
+        Expr::Macro(i) => {
+            rename_calls_in_macro(&mut i.mac, renames);
+        },
+        Expr::Verbatim(_) => (),
+        Expr::Lit(_) => (),
+        Expr::Infer(_) => (),
+        Expr::Continue(_) => (),
+        _ => (),
+    }
+}
+
+/// assert_eq!(self.f(), 1) のように、マクロの引数の中の呼び出しも書き換える。
+/// 引数を式の並びとして読めないマクロはそのままにする
+fn rename_calls_in_macro(mac: &mut Macro, renames: &[PathRewrite]) {
+    let _ = map_macro_exprs(mac, |expr| {
+        rename_calls(expr, renames);
+        Ok(())
+    });
+}
+
+/// path が renames の from と一致すれば、最後の名前を to のものにする。
+/// ターボフィッシュ (Self::f::<T>) はそのまま残す
+fn rename_path(path: &mut Path, renames: &[PathRewrite]) {
+    let Some(to) = renames.iter().find(|r| r.from == *path).and_then(|r| r.to.last_ident()) else {
+        return;
+    };
+    if let Some(last) = path.segments.last_mut() {
+        last.ident = Ident::new(&to.to_string(), last.ident.span());
+    }
+}
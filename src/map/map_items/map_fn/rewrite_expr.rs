@@ -0,0 +1,295 @@
+use quote::ToTokens;
+use syn::{Block, Expr, ExprAwait, ExprMethodCall, ExprTry, Local, Macro, Stmt};
+use crate::{map::map_items::map_fn::get_attrs::get_attrs_mut_from_expr, map_macro_exprs, ExprRewrite, PostfixOp};
+
+
+pub fn rewrite_expr_in_block(block: &mut Block, rewrites: &[ExprRewrite]) {
+    for stmt in &mut block.stmts {
+        rewrite_expr_in_stmt(stmt, rewrites);
+    }
+}
+
+pub fn rewrite_expr_in_stmt(stmt: &mut Stmt, rewrites: &[ExprRewrite]) {
+    match stmt {
+        Stmt::Local(i) => rewrite_expr_in_local(i, rewrites),
+        Stmt::Expr(i, _) => rewrite_expr(i, rewrites),
+        Stmt::Macro(i) => rewrite_expr_in_macro(&mut i.mac, rewrites),
+        Stmt::Item(_) => (),
+    }
+}
+
+pub fn rewrite_expr_in_local(local: &mut Local, rewrites: &[ExprRewrite]) {
+    if let Some(init) = &mut local.init {
+        rewrite_expr(&mut init.expr, rewrites);
+        if let Some((_, diverge)) = &mut init.diverge {
+            rewrite_expr(diverge, rewrites);
+        }
+    }
+}
+
+pub fn rewrite_expr(expr: &mut Expr, rewrites: &[ExprRewrite]) {
+    // 置換後の式を再び走査すると to が from を含む場合に無限に置換されるので、
+    // 置換前にレシーバだけを走査する
+    for rewrite in rewrites {
+        if is_postfix_ops_matched(expr, &rewrite.from) {
+            let d = Expr::Verbatim(Default::default());
+            let mut old_expr = std::mem::replace(expr, d);
+            let attrs = get_attrs_mut_from_expr(&mut old_expr).map(std::mem::take).unwrap_or_default();
+            let mut receiver = take_receiver(old_expr, rewrite.from.len());
+            rewrite_expr(&mut receiver, rewrites);
+            *expr = apply_postfix_ops(receiver, &rewrite.to);
+
+            // 元の式に付いていた属性を引き継ぐ
+            if let Some(new_attrs) = get_attrs_mut_from_expr(expr) {
+                *new_attrs = attrs;
+            }
+            return;
+        }
+    }
+
+    match expr {
+        Expr::Array(i) => {
+            for e in &mut i.elems {
+                rewrite_expr(e, rewrites);
+            }
+        },
+        Expr::Assign(i) => {
+            rewrite_expr(&mut i.left, rewrites);
+            rewrite_expr(&mut i.right, rewrites);
+        },
+        Expr::Async(i) => {
+            rewrite_expr_in_block(&mut i.block, rewrites);
+        },
+        Expr::Await(i) => {
+            rewrite_expr(&mut i.base, rewrites);
+        },
+        Expr::Binary(i) => {
+            rewrite_expr(&mut i.left, rewrites);
+            rewrite_expr(&mut i.right, rewrites);
+        },
+        Expr::Block(i) => {
+            rewrite_expr_in_block(&mut i.block, rewrites);
+        },
+        Expr::Break(i) => {
+            if let Some(e) = &mut i.expr {
+                rewrite_expr(e, rewrites);
+            }
+        },
+        Expr::Call(i) => {
+            rewrite_expr(&mut i.func, rewrites);
+            for e in &mut i.args {
+                rewrite_expr(e, rewrites);
+            }
+        },
+        Expr::Cast(i) => {
+            rewrite_expr(&mut i.expr, rewrites);
+        },
+        Expr::Closure(i) => {
+            rewrite_expr(&mut i.body, rewrites);
+        },
+        Expr::Const(i) => {
+            rewrite_expr_in_block(&mut i.block, rewrites);
+        },
+        Expr::Field(i) => {
+            rewrite_expr(&mut i.base, rewrites);
+        },
+        Expr::ForLoop(i) => {
+            rewrite_expr(&mut i.expr, rewrites);
+            rewrite_expr_in_block(&mut i.body, rewrites);
+        },
+        Expr::Group(i) => {
+            rewrite_expr(&mut i.expr, rewrites);
+        },
+        Expr::If(i) => {
+            rewrite_expr_in_block(&mut i.then_branch, rewrites);
+            rewrite_expr(&mut i.cond, rewrites);
+            if let Some((_, e)) = &mut i.else_branch {
+                rewrite_expr(e, rewrites);
+            }
+        },
+        Expr::Index(i) => {
+            rewrite_expr(&mut i.expr, rewrites);
+            rewrite_expr(&mut i.index, rewrites);
+        },
+        Expr::Let(i) => {
+            rewrite_expr(&mut i.expr, rewrites);
+        },
+        Expr::Loop(i) => {
+            rewrite_expr_in_block(&mut i.body, rewrites);
+        },
+        Expr::Match(i) => {
+            rewrite_expr(&mut i.expr, rewrites);
+            for a in &mut i.arms {
+                rewrite_expr(&mut a.body, rewrites);
+                if let Some((_, g)) = &mut a.guard {
+                    rewrite_expr(g, rewrites);
+                }
+            }
+        },
+        Expr::MethodCall(i) => {
+            rewrite_expr(&mut i.receiver, rewrites);
+            for a in &mut i.args {
+                rewrite_expr(a, rewrites);
+            }
+        },
+        Expr::Paren(i) => {
+            rewrite_expr(&mut i.expr, rewrites);
+        },
+        Expr::Range(i) => {
+            if let Some(s) = &mut i.start {
+                rewrite_expr(s, rewrites);
+            }
+            if let Some(e) = &mut i.end {
+                rewrite_expr(e, rewrites);
+            }
+        },
+        Expr::RawAddr(i) => {
+            rewrite_expr(&mut i.expr, rewrites);
+        },
+        Expr::Reference(i) => {
+            rewrite_expr(&mut i.expr, rewrites);
+        },
+        Expr::Repeat(i) => {
+            rewrite_expr(&mut i.expr, rewrites);
+            rewrite_expr(&mut i.len, rewrites);
+        },
+        Expr::Return(i) => {
+            if let Some(e) = &mut i.expr {
+                rewrite_expr(e, rewrites);
+            }
+        },
+        Expr::Struct(i) => {
+            for f in &mut i.fields {
+                rewrite_expr(&mut f.expr, rewrites);
+            }
+            if let Some(r) = &mut i.rest {
+                rewrite_expr(r, rewrites);
+            }
+        },
+        Expr::Try(i) => {
+            rewrite_expr(&mut i.expr, rewrites);
+        },
+        Expr::TryBlock(i) => {
+            rewrite_expr_in_block(&mut i.block, rewrites);
+        },
+        Expr::Tuple(i) => {
+            for e in &mut i.elems {
+                rewrite_expr(e, rewrites);
+            }
+        },
+        Expr::Unary(i) => {
+            rewrite_expr(&mut i.expr, rewrites);
+        },
+        Expr::Unsafe(i) => {
+            rewrite_expr_in_block(&mut i.block, rewrites);
+        },
+        Expr::While(i) => {
+            rewrite_expr(&mut i.cond, rewrites);
+            rewrite_expr_in_block(&mut i.body, rewrites);
+        },
+        Expr::Yield(i) => {
+            if let Some(e) = &mut i.expr {
+                rewrite_expr(e, rewrites);
+            }
+        },
+        Expr::Verbatim(_) => (),
+        Expr::Path(_) => (),
+        Expr::Macro(i) => {
+            rewrite_expr_in_macro(&mut i.mac, rewrites);
+        },
+        Expr::Lit(_) => (),
+        Expr::Infer(_) => (),
+        Expr::Continue(_) => (),
+        _ => (),
+    }
+}
+
+/// assert_eq!(*m.lock().await, 1) のように、マクロの引数の中の式も書き換える。
+/// 引数を式の並びとして読めないマクロはそのままにする
+pub fn rewrite_expr_in_macro(mac: &mut Macro, rewrites: &[ExprRewrite]) {
+    if rewrites.is_empty() {
+        return;
+    }
+    let _ = map_macro_exprs(mac, |expr| {
+        rewrite_expr(expr, rewrites);
+        Ok(())
+    });
+}
+
+/// expr が `<レシーバ><ops>` の形になっているか
+fn is_postfix_ops_matched(expr: &Expr, ops: &[PostfixOp]) -> bool {
+    let Some((last, rest)) = ops.split_last() else {
+        return true;
+    };
+
+    let base = match (expr, last) {
+        (Expr::Await(e), PostfixOp::Await) => &e.base,
+        (Expr::Try(e), PostfixOp::Try) => &e.expr,
+        (Expr::MethodCall(e), PostfixOp::MethodCall { method, turbofish, args }) => {
+            if &e.method != method {
+                return false;
+            }
+            // パターン側にターボフィッシュがない場合はどのターボフィッシュとも一致させる
+            if let Some(t) = turbofish {
+                let Some(et) = &e.turbofish else { return false };
+                if et.to_token_stream().to_string() != t.to_token_stream().to_string() {
+                    return false;
+                }
+            }
+            if e.args.to_token_stream().to_string() != args.to_token_stream().to_string() {
+                return false;
+            }
+            &e.receiver
+        },
+        _ => return false,
+    };
+
+    is_postfix_ops_matched(base, rest)
+}
+
+/// 末尾から n 個の後置演算を取り除いたレシーバを返す
+fn take_receiver(expr: Expr, n: usize) -> Expr {
+    if n == 0 {
+        return expr;
+    }
+
+    let base = match expr {
+        Expr::Await(e) => *e.base,
+        Expr::Try(e) => *e.expr,
+        Expr::MethodCall(e) => *e.receiver,
+        e => return e,
+    };
+
+    take_receiver(base, n - 1)
+}
+
+fn apply_postfix_ops(receiver: Expr, ops: &[PostfixOp]) -> Expr {
+    let mut expr = receiver;
+
+    for op in ops {
+        expr = match op {
+            PostfixOp::Await => Expr::Await(ExprAwait {
+                attrs: Vec::new(),
+                base: Box::new(expr),
+                dot_token: Default::default(),
+                await_token: Default::default(),
+            }),
+            PostfixOp::Try => Expr::Try(ExprTry {
+                attrs: Vec::new(),
+                expr: Box::new(expr),
+                question_token: Default::default(),
+            }),
+            PostfixOp::MethodCall { method, turbofish, args } => Expr::MethodCall(ExprMethodCall {
+                attrs: Vec::new(),
+                receiver: Box::new(expr),
+                dot_token: Default::default(),
+                method: method.clone(),
+                turbofish: turbofish.clone(),
+                paren_token: Default::default(),
+                args: args.clone(),
+            }),
+        };
+    }
+
+    expr
+}
@@ -1,9 +1,16 @@
 mod map_fn;
 
-use map_fn::*;
-use syn::{ImplItem, Item, Result};
+pub use map_fn::*;
+use proc_macro2::Span;
+use syn::{
+    Attribute, Block, Ident, ImplItem, Item, ItemEnum, ItemImpl, ItemMod, ItemStruct, ItemTrait, 
+    Result, Signature, TraitItem, Type
+};
 
-use crate::ItemImport;
+use crate::{
+    format_ident_with_template, map_container_attrs, map_fields, map_generics, map_variants, select_variant_attrs, 
+    FnArgs, FnMarker, PathRewrite, PathWithoutArgs, VariantArgs
+};
 
 
 pub struct SyncAsyncItems<T> {
@@ -11,24 +18,161 @@ pub struct SyncAsyncItems<T> {
     pub async_items: Vec<T>
 }
 
+/// コンテナごとに計算される、中の項目の変換に使う情報
+#[derive(Clone, Default)]
+pub struct ContainerContext {
+    pub fn_args: FnArgs,
+    /// auto_await で .await を付ける関数の呼び出しのパス。
+    /// モジュールの関数は f、impl やトレイトの関数は Self::f と Foo::f
+    pub awaited_fns: Vec<PathWithoutArgs>,
+    /// auto_await で .await が付く関数の元の名前のパス。infer で印を推測するときに使うので、sync でも設定する
+    pub auto_awaited_fns: Vec<PathWithoutArgs>,
+    /// #[maybe_async(sync_name = ...)] などで名前が変わる関数。呼び出しとドキュメントのリンクの書き換えに使う
+    pub fn_renames: Vec<PathRewrite>,
+    /// 印のない関数をエラーにせずそのまま残す。ネストした項目の中で使う
+    pub keep_unmarked: bool,
+    /// ネストしたモジュールの先頭に注入する use と type
+    pub mod_items: Vec<Item>,
+}
+
+impl ContainerContext {
+
+    pub fn new(fn_args: FnArgs) -> ContainerContext {
+        ContainerContext { fn_args, ..Default::default() }
+    }
+}
+
+/// ctx_for_sync / ctx_for_async は mod_context で作ったもの
 pub fn map_mod_items(
     items: Vec<Item>,
-    item_imports_for_sync: &[ItemImport],
-    item_imports_for_async: &[ItemImport]
+    args_for_sync: &VariantArgs,
+    args_for_async: &VariantArgs,
+    ctx_for_sync: &ContainerContext,
+    ctx_for_async: &ContainerContext,
 ) -> Result<SyncAsyncItems<Item>> {
 
+    // ネストした項目の中の印のない関数 (impl Display の fmt など) はそのまま残す
+    let nested_ctx_for_sync = &ContainerContext { keep_unmarked: true, ..ctx_for_sync.clone() };
+    let nested_ctx_for_async = &ContainerContext { keep_unmarked: true, ..ctx_for_async.clone() };
+
     let mut sync_items = Vec::new();
     let mut async_items = Vec::new();
 
     for item in items {
         match item {
             Item::Fn(item_fn) => {
-                sync_items.push(map_mod_fn(item_fn.clone(), false, item_imports_for_sync)?);
-                async_items.push(map_mod_fn(item_fn, true, item_imports_for_async)?);
+                sync_items.push(map_mod_fn(item_fn.clone(), false, args_for_sync, ctx_for_sync)?);
+                async_items.push(map_mod_fn(item_fn, true, args_for_async, ctx_for_async)?);
+            }
+            Item::Mod(ItemMod { attrs, vis, unsafety, mod_token, ident, content: Some((brace, items)), semi }) => {
+                let (s, a) = mod_context(&items, nested_ctx_for_sync, nested_ctx_for_async);
+                let attrs = map_container_attrs(attrs, args_for_sync, args_for_async, &s, &a)?;
+                let i = map_mod_items(items, args_for_sync, args_for_async, &s, &a)?;
+
+                // 引数の use や type をネストしたモジュールの中でも使えるようにする
+                let mut sync_content = ctx_for_sync.mod_items.clone();
+                let mut async_content = ctx_for_async.mod_items.clone();
+                sync_content.extend(i.sync_items);
+                async_content.extend(i.async_items);
+
+                sync_items.push(Item::Mod(ItemMod { 
+                    attrs: attrs.sync_attrs, 
+                    vis: vis.clone(), 
+                    unsafety, 
+                    mod_token, 
+                    ident: ident.clone(), 
+                    content: Some((brace, sync_content)), 
+                    semi 
+                }));
+                async_items.push(Item::Mod(ItemMod { 
+                    attrs: attrs.async_attrs, 
+                    vis, 
+                    unsafety, 
+                    mod_token, 
+                    ident, 
+                    content: Some((brace, async_content)), 
+                    semi 
+                }));
+            }
+            Item::Impl(item_impl) => {
+                let (s, a) = impl_context(
+                    &item_impl.items, 
+                    &item_impl.self_ty, 
+                    nested_ctx_for_sync, 
+                    nested_ctx_for_async
+                );
+                let attrs = map_container_attrs(item_impl.attrs.clone(), args_for_sync, args_for_async, &s, &a)?;
+                let i = map_impl_items(item_impl.items.clone(), args_for_sync, args_for_async, &s, &a)?;
+                sync_items.push(Item::Impl(ItemImpl { 
+                    attrs: attrs.sync_attrs, 
+                    items: i.sync_items, 
+                    ..item_impl.clone() 
+                }));
+                async_items.push(Item::Impl(ItemImpl { 
+                    attrs: attrs.async_attrs, 
+                    items: i.async_items, 
+                    ..item_impl 
+                }));
+            }
+            Item::Struct(item_struct) => {
+                let g = map_generics(item_struct.generics.clone(), args_for_sync, args_for_async)?;
+                let f = map_fields(item_struct.fields.clone(), args_for_sync, args_for_async)?;
+                sync_items.push(Item::Struct(ItemStruct { 
+                    attrs: select_variant_attrs(item_struct.attrs.clone(), false)?, 
+                    generics: g.sync_generics, 
+                    fields: f.sync_fields, 
+                    ..item_struct.clone() 
+                }));
+                async_items.push(Item::Struct(ItemStruct { 
+                    attrs: select_variant_attrs(item_struct.attrs.clone(), true)?, 
+                    generics: g.async_generics, 
+                    fields: f.async_fields, 
+                    ..item_struct 
+                }));
+            }
+            Item::Enum(item_enum) => {
+                let g = map_generics(item_enum.generics.clone(), args_for_sync, args_for_async)?;
+                let v = map_variants(item_enum.variants.clone(), args_for_sync, args_for_async)?;
+                sync_items.push(Item::Enum(ItemEnum { 
+                    attrs: select_variant_attrs(item_enum.attrs.clone(), false)?, 
+                    generics: g.sync_generics, 
+                    variants: v.sync_variants, 
+                    ..item_enum.clone() 
+                }));
+                async_items.push(Item::Enum(ItemEnum { 
+                    attrs: select_variant_attrs(item_enum.attrs.clone(), true)?, 
+                    generics: g.async_generics, 
+                    variants: v.async_variants, 
+                    ..item_enum 
+                }));
+            }
+            Item::Trait(item_trait) => {
+                let (s, a) = trait_context(
+                    &item_trait.items, 
+                    &item_trait.ident, 
+                    nested_ctx_for_sync, 
+                    nested_ctx_for_async
+                );
+                let attrs = map_container_attrs(item_trait.attrs.clone(), args_for_sync, args_for_async, &s, &a)?;
+                let i = map_trait_items(item_trait.items.clone(), args_for_sync, args_for_async, &s, &a)?;
+                sync_items.push(Item::Trait(ItemTrait { 
+                    attrs: attrs.sync_attrs, 
+                    items: i.sync_items, 
+                    ..item_trait.clone() 
+                }));
+                async_items.push(Item::Trait(ItemTrait { 
+                    attrs: attrs.async_attrs, 
+                    items: i.async_items, 
+                    ..item_trait 
+                }));
             }
-            _ => {
-                sync_items.push(item.clone());
-                async_items.push(item);
+            // const, static, use などは属性の選択だけを行う
+            mut item => {
+                let mut async_item = item.clone();
+                select_item_attrs(&mut item, false)?;
+                select_item_attrs(&mut async_item, true)?;
+                sync_items.push(item);
+                async_items.push(async_item);
             }
         }
     }
@@ -36,10 +180,13 @@ pub fn map_mod_items(
     Ok(SyncAsyncItems { sync_items, async_items })
 }
 
+/// ctx_for_sync / ctx_for_async は impl_context で作ったもの
 pub fn map_impl_items(
     items: Vec<ImplItem>,
-    item_imports_for_sync: &[ItemImport],
-    item_imports_for_async: &[ItemImport],
+    args_for_sync: &VariantArgs,
+    args_for_async: &VariantArgs,
+    ctx_for_sync: &ContainerContext,
+    ctx_for_async: &ContainerContext,
 ) -> Result<SyncAsyncItems<ImplItem>> {
 
     let mut sync_items = Vec::new();
@@ -48,15 +195,233 @@ pub fn map_impl_items(
     for item in items {
         match item {
             ImplItem::Fn(item_fn) => {
-                sync_items.push(map_impl_fn(item_fn.clone(), false, item_imports_for_sync)?);
-                async_items.push(map_impl_fn(item_fn, true, item_imports_for_async)?);
+                sync_items.push(map_impl_fn(item_fn.clone(), false, args_for_sync, ctx_for_sync)?);
+                async_items.push(map_impl_fn(item_fn, true, args_for_async, ctx_for_async)?);
             }
-            _ => {
-                sync_items.push(item.clone());
-                async_items.push(item);
+            // const, type などは属性の選択だけを行う
+            mut item => {
+                let mut async_item = item.clone();
+                select_impl_item_attrs(&mut item, false)?;
+                select_impl_item_attrs(&mut async_item, true)?;
+                sync_items.push(item);
+                async_items.push(async_item);
             }
         }
     }
 
     Ok(SyncAsyncItems { sync_items, async_items })
-}
\ No newline at end of file
+}
+
+/// ctx_for_sync / ctx_for_async は trait_context で作ったもの
+pub fn map_trait_items(
+    items: Vec<TraitItem>,
+    args_for_sync: &VariantArgs,
+    args_for_async: &VariantArgs,
+    ctx_for_sync: &ContainerContext,
+    ctx_for_async: &ContainerContext,
+) -> Result<SyncAsyncItems<TraitItem>> {
+
+    let mut sync_items = Vec::new();
+    let mut async_items = Vec::new();
+
+    for item in items {
+        match item {
+            TraitItem::Fn(item_fn) => {
+                sync_items.push(map_trait_fn(item_fn.clone(), false, args_for_sync, ctx_for_sync)?);
+                async_items.push(map_trait_fn(item_fn, true, args_for_async, ctx_for_async)?);
+            }
+            // const, type などは属性の選択だけを行う
+            mut item => {
+                let mut async_item = item.clone();
+                select_trait_item_attrs(&mut item, false)?;
+                select_trait_item_attrs(&mut async_item, true)?;
+                sync_items.push(item);
+                async_items.push(async_item);
+            }
+        }
+    }
+
+    Ok(SyncAsyncItems { sync_items, async_items })
+}
+
+fn select_item_attrs(item: &mut Item, asyncness: bool) -> Result<()> {
+    let attrs = match item {
+        Item::Const(i) => &mut i.attrs,
+        Item::Enum(i) => &mut i.attrs,
+        Item::ExternCrate(i) => &mut i.attrs,
+        Item::Fn(i) => &mut i.attrs,
+        Item::ForeignMod(i) => &mut i.attrs,
+        Item::Impl(i) => &mut i.attrs,
+        Item::Macro(i) => &mut i.attrs,
+        Item::Mod(i) => &mut i.attrs,
+        Item::Static(i) => &mut i.attrs,
+        Item::Struct(i) => &mut i.attrs,
+        Item::Trait(i) => &mut i.attrs,
+        Item::TraitAlias(i) => &mut i.attrs,
+        Item::Type(i) => &mut i.attrs,
+        Item::Union(i) => &mut i.attrs,
+        Item::Use(i) => &mut i.attrs,
+        _ => return Ok(()),
+    };
+    *attrs = select_variant_attrs(std::mem::take(attrs), asyncness)?;
+    Ok(())
+}
+
+fn select_impl_item_attrs(item: &mut ImplItem, asyncness: bool) -> Result<()> {
+    let attrs = match item {
+        ImplItem::Const(i) => &mut i.attrs,
+        ImplItem::Fn(i) => &mut i.attrs,
+        ImplItem::Type(i) => &mut i.attrs,
+        ImplItem::Macro(i) => &mut i.attrs,
+        _ => return Ok(()),
+    };
+    *attrs = select_variant_attrs(std::mem::take(attrs), asyncness)?;
+    Ok(())
+}
+
+fn select_trait_item_attrs(item: &mut TraitItem, asyncness: bool) -> Result<()> {
+    let attrs = match item {
+        TraitItem::Const(i) => &mut i.attrs,
+        TraitItem::Fn(i) => &mut i.attrs,
+        TraitItem::Type(i) => &mut i.attrs,
+        TraitItem::Macro(i) => &mut i.attrs,
+        _ => return Ok(()),
+    };
+    *attrs = select_variant_attrs(std::mem::take(attrs), asyncness)?;
+    Ok(())
+}
+
+/// モジュールの中の関数について、親のコンテキストに関数の情報を追加した (sync, async) のコンテキストを返す
+pub fn mod_context(
+    items: &[Item],
+    ctx_for_sync: &ContainerContext,
+    ctx_for_async: &ContainerContext,
+) -> (ContainerContext, ContainerContext) {
+
+    let fns = items.iter().filter_map(|i| match i {
+        Item::Fn(f) => Some((f.attrs.as_slice(), &f.sig, Some(&*f.block))),
+        _ => None,
+    });
+    with_container_fns(ctx_for_sync, ctx_for_async, fns, &[Vec::new()])
+}
+
+/// impl の中の関数について同様のコンテキストを返す。
+/// 名前の書き換えは Self::f と、型の名前を付けた Foo::f の形で行う
+pub fn impl_context(
+    items: &[ImplItem],
+    self_ty: &Type,
+    ctx_for_sync: &ContainerContext,
+    ctx_for_async: &ContainerContext,
+) -> (ContainerContext, ContainerContext) {
+
+    let mut prefixes = vec![vec![Ident::new("Self", Span::call_site())]];
+    if let Type::Path(p) = self_ty {
+        if p.qself.is_none() {
+            prefixes.push(p.path.segments.iter().map(|s| s.ident.clone()).collect());
+        }
+    }
+
+    let fns = items.iter().filter_map(|i| match i {
+        ImplItem::Fn(f) => Some((f.attrs.as_slice(), &f.sig, Some(&f.block))),
+        _ => None,
+    });
+    with_container_fns(ctx_for_sync, ctx_for_async, fns, &prefixes)
+}
+
+/// トレイトの中の関数について同様のコンテキストを返す。
+/// 名前の書き換えは Self::f と、トレイトの名前を付けた Foo::f の形で行う
+pub fn trait_context(
+    items: &[TraitItem],
+    ident: &Ident,
+    ctx_for_sync: &ContainerContext,
+    ctx_for_async: &ContainerContext,
+) -> (ContainerContext, ContainerContext) {
+
+    let prefixes = [vec![Ident::new("Self", Span::call_site())], vec![ident.clone()]];
+
+    let fns = items.iter().filter_map(|i| match i {
+        TraitItem::Fn(f) => Some((f.attrs.as_slice(), &f.sig, f.default.as_ref())),
+        _ => None,
+    });
+    with_container_fns(ctx_for_sync, ctx_for_async, fns, &prefixes)
+}
+
+/// コンテナ内の関数について、auto_await で .await を付ける関数の名前と、
+/// 名前が変わる関数の呼び出しやリンクの書き換えを設定した (sync, async) のコンテキストを返す。
+/// sync バリアントでは .await が取り除かれるので awaited_fns は設定しない。
+/// 書き換えは prefixes のそれぞれを関数名の前に付けたパスについて行う
+fn with_container_fns<'a>(
+    ctx_for_sync: &ContainerContext,
+    ctx_for_async: &ContainerContext,
+    fns: impl Iterator<Item = (&'a [Attribute], &'a Signature, Option<&'a Block>)>,
+    prefixes: &[Vec<Ident>],
+) -> (ContainerContext, ContainerContext) {
+
+    // 外側のモジュールの関数も呼べるので、親のコンテキストの関数に追加する
+    let mut sync_ctx = ctx_for_sync.clone();
+    let mut async_ctx = ctx_for_async.clone();
+
+    let fn_path = |prefix: &[Ident], ident: &Ident| {
+        let mut idents = prefix.to_vec();
+        idents.push(ident.clone());
+        PathWithoutArgs::from_idents(idents, false)
+    };
+
+    let fn_paths = |ident: &Ident| prefixes.iter().map(|prefix| fn_path(prefix, ident)).collect::<Vec<_>>();
+
+    let fns: Vec<_> = fns.collect();
+    let fn_args = &ctx_for_async.fn_args;
+
+    // auto_await では .await を書かずに呼ぶので、infer ではそれらの関数の呼び出しも推測に使う。
+    // 推測で async になる関数が増えると、それを呼ぶ関数も async になるので、増えなくなるまで繰り返す
+    let mut awaited = Vec::new();
+    let mut auto_awaited_fns = ctx_for_async.auto_awaited_fns.clone();
+    if fn_args.auto_await {
+        loop {
+            let len = awaited.len();
+            for (attrs, sig, block) in &fns {
+                if !awaited.contains(&sig.ident) && matches!(
+                    peek_fn_marker(attrs, sig, *block, fn_args, &auto_awaited_fns),
+                    Some(FnMarker::MaybeAsync | FnMarker::AlwaysAsync)
+                ) {
+                    awaited.push(sig.ident.clone());
+                    auto_awaited_fns.extend(fn_paths(&sig.ident));
+                }
+            }
+            if !fn_args.infer_marker || awaited.len() == len {
+                break;
+            }
+        }
+    }
+
+    for (attrs, sig, _) in fns {
+        let (sync_name, async_name) = peek_fn_name_templates(attrs);
+        let mut async_ident = sig.ident.clone();
+        for (ctx, name, new_ident) in [
+            (&mut sync_ctx, sync_name, None), 
+            (&mut async_ctx, async_name, Some(&mut async_ident))
+        ] {
+            let Some(name) = name else { continue };
+            let ident = format_ident_with_template(&name.value(), &sig.ident);
+            if ident == sig.ident {
+                continue;
+            }
+            for prefix in prefixes {
+                ctx.fn_renames.push(PathRewrite { from: fn_path(prefix, &sig.ident), to: fn_path(prefix, &ident) });
+            }
+            if let Some(new_ident) = new_ident {
+                *new_ident = ident;
+            }
+        }
+
+        // 呼び出しは先に新しい名前に書き換えられるので、新しい名前で .await を付ける
+        if awaited.contains(&sig.ident) {
+            async_ctx.awaited_fns.extend(fn_paths(&async_ident));
+        }
+    }
+
+    sync_ctx.auto_awaited_fns = auto_awaited_fns.clone();
+    async_ctx.auto_awaited_fns = auto_awaited_fns;
+
+    (sync_ctx, async_ctx)
+}
@@ -0,0 +1,28 @@
+use syn::{Path, Result};
+use crate::{map::replace_item_name::{replace_item_name_in_path, replaces_from_variant_args}, VariantArgs};
+
+
+pub struct SyncAsyncPath {
+    pub sync_path: Path,
+    pub async_path: Path
+}
+
+pub fn map_path(
+    path: Path,
+    args_for_sync: &VariantArgs,
+    args_for_async: &VariantArgs
+) -> Result<SyncAsyncPath> {
+
+    let mut sync_path = path.clone();
+    let mut async_path = path;
+    replace_item_name_in_path(
+        &mut sync_path, 
+        &replaces_from_variant_args(args_for_sync)
+    );
+    replace_item_name_in_path(
+        &mut async_path, 
+        &replaces_from_variant_args(args_for_async)
+    );
+
+    Ok(SyncAsyncPath { sync_path, async_path })
+}
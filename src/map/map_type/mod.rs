@@ -1,7 +1,7 @@
 use std::borrow::Cow;
 use quote::quote;
 use syn::{Result, Type};
-use crate::map::replace_item_name::{replace_item_name_in_type, ReplaceItemNameFromTo};
+use crate::{map::replace_item_name::{replace_item_name_in_type, replaces_from_variant_args, ReplaceItemNameFromTo, ReplaceItemNameTo}, format_name, variant_templates, NameCase, VariantArgs};
 
 
 pub struct SyncAsyncType {
@@ -16,7 +16,9 @@ pub struct SyncAsyncIdentOfType {
 }
 
 pub fn map_type_to_ident(
-    ty: &Type
+    ty: &Type,
+    args_for_sync: &VariantArgs,
+    args_for_async: &VariantArgs
 ) -> Result<SyncAsyncIdentOfType> {
 
     let ident = {
@@ -26,22 +28,25 @@ pub fn map_type_to_ident(
             .map(|(i, _)| i.to_string())
             .unwrap_or(ty_str)
     };
-    let sync_ident = format!("Sync{ident}");
-    let async_ident = format!("Async{ident}");
+    let (sync_template, async_template) = variant_templates(NameCase::UpperCamel, args_for_sync, args_for_async)?;
+    let sync_ident = format_name(&sync_template, &ident);
+    let async_ident = format_name(&async_template, &ident);
 
     Ok(SyncAsyncIdentOfType { sync_ident, async_ident, ident })
 }
 
 pub fn map_type(
-    ty: Type
+    ty: Type,
+    args_for_sync: &VariantArgs,
+    args_for_async: &VariantArgs
 ) -> Result<SyncAsyncType> {
 
-    let SyncAsyncIdentOfType { sync_ident, async_ident, ident } = map_type_to_ident(&ty)?;
+    let SyncAsyncIdentOfType { sync_ident, async_ident, ident } = map_type_to_ident(&ty, args_for_sync, args_for_async)?;
 
     let replaces = |from: &str, to: &str| -> Result<Vec<ReplaceItemNameFromTo<'_>>> {
         Ok(vec![ReplaceItemNameFromTo { 
             from: Cow::Owned(syn::parse_str(from)?), 
-            to: Cow::Owned(syn::parse_str(to)?) 
+            to: ReplaceItemNameTo::Path(Cow::Owned(syn::parse_str(to)?)),
         }])
     };
 
@@ -50,5 +55,25 @@ pub fn map_type(
     replace_item_name_in_type(&mut sync_ty, &replaces(&ident, &sync_ident)?);
     replace_item_name_in_type(&mut async_ty, &replaces(&ident, &async_ident)?);
    
+    Ok(SyncAsyncType { sync_ty, async_ty })
+}
+
+pub fn map_type_by_variant_args(
+    ty: Type,
+    args_for_sync: &VariantArgs,
+    args_for_async: &VariantArgs
+) -> Result<SyncAsyncType> {
+
+    let mut sync_ty = ty.clone();
+    let mut async_ty = ty;
+    replace_item_name_in_type(
+        &mut sync_ty, 
+        &replaces_from_variant_args(args_for_sync)
+    );
+    replace_item_name_in_type(
+        &mut async_ty, 
+        &replaces_from_variant_args(args_for_async)
+    );
+
     Ok(SyncAsyncType { sync_ty, async_ty })
 }
\ No newline at end of file
@@ -0,0 +1,39 @@
+use syn::{punctuated::Punctuated, Result, Token, Variant};
+use crate::{map_attrs, map_fields, VariantArgs};
+
+
+pub struct SyncAsyncVariants {
+    pub sync_variants: Punctuated<Variant, Token![,]>,
+    pub async_variants: Punctuated<Variant, Token![,]>
+}
+
+pub fn map_variants(
+    variants: Punctuated<Variant, Token![,]>,
+    args_for_sync: &VariantArgs,
+    args_for_async: &VariantArgs
+) -> Result<SyncAsyncVariants> {
+
+    let mut sync_variants = Punctuated::new();
+    let mut async_variants = Punctuated::new();
+
+    for variant in variants {
+        let a = map_attrs(variant.attrs, args_for_sync, args_for_async)?;
+        let f = map_fields(variant.fields, args_for_sync, args_for_async)?;
+
+        // discriminant はそのまま残す
+        sync_variants.push(Variant {
+            attrs: a.sync_attrs,
+            ident: variant.ident.clone(),
+            fields: f.sync_fields,
+            discriminant: variant.discriminant.clone(),
+        });
+        async_variants.push(Variant {
+            attrs: a.async_attrs,
+            ident: variant.ident,
+            fields: f.async_fields,
+            discriminant: variant.discriminant,
+        });
+    }
+
+    Ok(SyncAsyncVariants { sync_variants, async_variants })
+}
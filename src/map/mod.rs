@@ -1,16 +1,24 @@
 mod map_attrs;
+mod map_bounds;
+mod map_expr;
 mod map_fileds;
 mod map_generics;
 mod map_items;
+mod map_path;
 mod map_type;
+mod map_variants;
 mod replace_item_name;
 mod replace_item_name_in_doc;
 
 pub use map_attrs::*;
+pub use map_bounds::*;
+pub use map_expr::*;
 pub use map_fileds::*;
 pub use map_generics::*;
 pub use map_items::*;
+pub use map_path::*;
 pub use map_type::*;
+pub use map_variants::*;
 
 pub(crate) use replace_item_name_in_doc::*;
 pub(crate) use replace_item_name::*;
\ No newline at end of file
@@ -1,31 +1,95 @@
-use crate::{ItemImport, PathWithoutArgs};
-use std::borrow::Cow;
+use crate::{map_macro_exprs, ItemImport, PathRewrite, PathWithoutArgs, TypeAlias, VariantArgs};
+use std::{borrow::Cow, collections::HashMap};
+use proc_macro2::{Delimiter, Group, Spacing, TokenStream, TokenTree};
+use quote::{quote, ToTokens};
 use syn::{
-    punctuated::Punctuated, Field, Fields, FnArg, GenericArgument, GenericParam, Generics, Ident, 
-    Macro, Pat, Path, PathArguments, PathSegment, ReturnType, Signature, Type, TypeParamBound, 
-    WherePredicate
+    punctuated::Punctuated, Block, Expr, Field, Fields, FnArg, GenericArgument, GenericParam, Generics, 
+    Macro, Pat, Path, PathArguments, PathSegment, ReturnType, Signature, Stmt, Type, TypePath, 
+    TypeParamBound, WherePredicate
 };
 
 
 pub struct ReplaceItemNameFromTo<'a> {
-    pub from: Cow<'a, Ident>,
-    pub to: Cow<'a, PathWithoutArgs>,
+    pub from: Cow<'a, PathWithoutArgs>,
+    pub to: ReplaceItemNameTo<'a>,
+}
+
+pub enum ReplaceItemNameTo<'a> {
+    Path(Cow<'a, PathWithoutArgs>),
+    /// from のジェネリクスの引数を埋め込んだ型に置き換える
+    Type(&'a TypeAlias),
+}
+
+impl ReplaceItemNameTo<'_> {
+
+    /// ドキュメントのリンク先に使うパス。
+    /// パスで表せない型の別名の場合は None
+    pub fn to_path_string(&self) -> Option<String> {
+        match self {
+            ReplaceItemNameTo::Path(p) => Some(p.to_string()),
+            ReplaceItemNameTo::Type(alias) => {
+                let Type::Path(TypePath { qself: None, path }) = &alias.ty else {
+                    return None;
+                };
+                let mut s = String::new();
+                if path.leading_colon.is_some() {
+                    s.push_str("::");
+                }
+                for (i, seg) in path.segments.iter().enumerate() {
+                    if i != 0 {
+                        s.push_str("::");
+                    }
+                    s.push_str(&seg.ident.to_string());
+                }
+                Some(s)
+            },
+        }
+    }
 }
 
 pub fn replaces_from_item_imports<'a>(items: &'a [ItemImport]) -> Vec<ReplaceItemNameFromTo<'a>> {
     let mut buf = Vec::with_capacity(items.len());
     for item in items {
-        let Some(from) = item.alias.as_ref().or_else(|| item.path.last_ident()) else {
+        // glob はどの名前を持ち込むか分からないので置換には使わない
+        let Some(from) = item.name() else {
             continue;
         };
 
-        let from = Cow::Borrowed(from);
-        let to = Cow::Borrowed(&item.path);
+        let from = Cow::Owned(PathWithoutArgs::from_idents(vec![from.clone()], false));
+        let to = ReplaceItemNameTo::Path(Cow::Borrowed(&item.path));
+        buf.push(ReplaceItemNameFromTo { from, to });
+    }
+    buf
+}
+
+pub fn replaces_from_path_rewrites<'a>(rewrites: &'a [PathRewrite]) -> Vec<ReplaceItemNameFromTo<'a>> {
+    let mut buf = Vec::with_capacity(rewrites.len());
+    for rewrite in rewrites {
+        let from = Cow::Borrowed(&rewrite.from);
+        let to = ReplaceItemNameTo::Path(Cow::Borrowed(&rewrite.to));
+        buf.push(ReplaceItemNameFromTo { from, to });
+    }
+    buf
+}
+
+pub fn replaces_from_type_aliases<'a>(aliases: &'a [TypeAlias]) -> Vec<ReplaceItemNameFromTo<'a>> {
+    let mut buf = Vec::with_capacity(aliases.len());
+    for alias in aliases {
+        let from = Cow::Owned(PathWithoutArgs::from_idents(vec![alias.ident.clone()], false));
+        let to = ReplaceItemNameTo::Type(alias);
         buf.push(ReplaceItemNameFromTo { from, to });
     }
     buf
 }
 
+/// 最初に一致したものが使われるので、より具体的な rewrite を use より先に並べる
+pub fn replaces_from_variant_args<'a>(args: &'a VariantArgs) -> Vec<ReplaceItemNameFromTo<'a>> {
+    let mut buf = replaces_from_path_rewrites(&args.path_rewrites);
+    buf.extend(replaces_from_type_aliases(&args.type_aliases));
+    buf.extend(replaces_from_item_imports(&args.item_imports));
+    buf
+}
+
 
 pub fn replace_item_name_in_signature<'a>(
     sig: &mut Signature, 
@@ -60,14 +124,14 @@ pub fn replace_item_name_in_pat<'a>(
     match pat {
         Pat::Ident(t) => {
             if let Some(t) = &mut t.subpat {
-                replace_item_name_in_pat(&mut *t.1, replaces);
+                replace_item_name_in_pat(&mut t.1, replaces);
             }
         },
         Pat::Macro(t) => replace_item_name_in_macro(&mut t.mac, replaces),
         Pat::Paren(t) => replace_item_name_in_pat(&mut t.pat, replaces),
         Pat::Path(t) => {
             if let Some(qself) = &mut t.qself {
-                replace_item_name_in_type(&mut *qself.ty, replaces);
+                replace_item_name_in_type(&mut qself.ty, replaces);
             }
             replace_item_name_in_path(&mut t.path, replaces)
         },
@@ -137,6 +201,196 @@ pub fn replace_item_name_in_macro<'a>(
 ) {
 
     replace_item_name_in_path(&mut m.path, replaces);
+
+    let _ = map_macro_exprs(m, |expr| {
+        replace_item_name_in_expr(expr, replaces);
+        Ok(())
+    });
+}
+
+pub fn replace_item_name_in_block<'a>(
+    block: &mut Block,
+    replaces: &[ReplaceItemNameFromTo<'a>]
+) {
+
+    for stmt in &mut block.stmts {
+        replace_item_name_in_stmt(stmt, replaces);
+    }
+}
+
+pub fn replace_item_name_in_stmt<'a>(
+    stmt: &mut Stmt,
+    replaces: &[ReplaceItemNameFromTo<'a>]
+) {
+
+    match stmt {
+        Stmt::Local(l) => {
+            replace_item_name_in_pat(&mut l.pat, replaces);
+            if let Some(init) = &mut l.init {
+                replace_item_name_in_expr(&mut init.expr, replaces);
+                if let Some((_, diverge)) = &mut init.diverge {
+                    replace_item_name_in_expr(diverge, replaces);
+                }
+            }
+        },
+        Stmt::Expr(e, _) => replace_item_name_in_expr(e, replaces),
+        Stmt::Macro(m) => replace_item_name_in_macro(&mut m.mac, replaces),
+        Stmt::Item(_) => (),
+    }
+}
+
+pub fn replace_item_name_in_expr<'a>(
+    expr: &mut Expr,
+    replaces: &[ReplaceItemNameFromTo<'a>]
+) {
+
+    match expr {
+        Expr::Array(i) => {
+            for e in &mut i.elems {
+                replace_item_name_in_expr(e, replaces);
+            }
+        },
+        Expr::Assign(i) => {
+            replace_item_name_in_expr(&mut i.left, replaces);
+            replace_item_name_in_expr(&mut i.right, replaces);
+        },
+        Expr::Async(i) => replace_item_name_in_block(&mut i.block, replaces),
+        Expr::Await(i) => replace_item_name_in_expr(&mut i.base, replaces),
+        Expr::Binary(i) => {
+            replace_item_name_in_expr(&mut i.left, replaces);
+            replace_item_name_in_expr(&mut i.right, replaces);
+        },
+        Expr::Block(i) => replace_item_name_in_block(&mut i.block, replaces),
+        Expr::Break(i) => {
+            if let Some(e) = &mut i.expr {
+                replace_item_name_in_expr(e, replaces);
+            }
+        },
+        Expr::Call(i) => {
+            replace_item_name_in_expr(&mut i.func, replaces);
+            for e in &mut i.args {
+                replace_item_name_in_expr(e, replaces);
+            }
+        },
+        Expr::Cast(i) => {
+            replace_item_name_in_expr(&mut i.expr, replaces);
+            replace_item_name_in_type(&mut i.ty, replaces);
+        },
+        Expr::Closure(i) => {
+            for input in &mut i.inputs {
+                replace_item_name_in_pat(input, replaces);
+            }
+            replace_item_name_in_return_type(&mut i.output, replaces);
+            replace_item_name_in_expr(&mut i.body, replaces);
+        },
+        Expr::Const(i) => replace_item_name_in_block(&mut i.block, replaces),
+        Expr::Field(i) => replace_item_name_in_expr(&mut i.base, replaces),
+        Expr::ForLoop(i) => {
+            replace_item_name_in_pat(&mut i.pat, replaces);
+            replace_item_name_in_expr(&mut i.expr, replaces);
+            replace_item_name_in_block(&mut i.body, replaces);
+        },
+        Expr::Group(i) => replace_item_name_in_expr(&mut i.expr, replaces),
+        Expr::If(i) => {
+            replace_item_name_in_expr(&mut i.cond, replaces);
+            replace_item_name_in_block(&mut i.then_branch, replaces);
+            if let Some((_, e)) = &mut i.else_branch {
+                replace_item_name_in_expr(e, replaces);
+            }
+        },
+        Expr::Index(i) => {
+            replace_item_name_in_expr(&mut i.expr, replaces);
+            replace_item_name_in_expr(&mut i.index, replaces);
+        },
+        Expr::Let(i) => {
+            replace_item_name_in_pat(&mut i.pat, replaces);
+            replace_item_name_in_expr(&mut i.expr, replaces);
+        },
+        Expr::Loop(i) => replace_item_name_in_block(&mut i.body, replaces),
+        Expr::Macro(i) => replace_item_name_in_macro(&mut i.mac, replaces),
+        Expr::Match(i) => {
+            replace_item_name_in_expr(&mut i.expr, replaces);
+            for a in &mut i.arms {
+                replace_item_name_in_pat(&mut a.pat, replaces);
+                if let Some((_, g)) = &mut a.guard {
+                    replace_item_name_in_expr(g, replaces);
+                }
+                replace_item_name_in_expr(&mut a.body, replaces);
+            }
+        },
+        Expr::MethodCall(i) => {
+            replace_item_name_in_expr(&mut i.receiver, replaces);
+            if let Some(t) = &mut i.turbofish {
+                for arg in &mut t.args {
+                    replace_item_name_in_generic_arguments(arg, replaces);
+                }
+            }
+            for a in &mut i.args {
+                replace_item_name_in_expr(a, replaces);
+            }
+        },
+        Expr::Paren(i) => replace_item_name_in_expr(&mut i.expr, replaces),
+        Expr::Path(i) => {
+            if let Some(qself) = &mut i.qself {
+                replace_item_name_in_type(&mut qself.ty, replaces);
+            }
+            replace_item_name_in_path(&mut i.path, replaces);
+        },
+        Expr::Range(i) => {
+            if let Some(s) = &mut i.start {
+                replace_item_name_in_expr(s, replaces);
+            }
+            if let Some(e) = &mut i.end {
+                replace_item_name_in_expr(e, replaces);
+            }
+        },
+        Expr::RawAddr(i) => replace_item_name_in_expr(&mut i.expr, replaces),
+        Expr::Reference(i) => replace_item_name_in_expr(&mut i.expr, replaces),
+        Expr::Repeat(i) => {
+            replace_item_name_in_expr(&mut i.expr, replaces);
+            replace_item_name_in_expr(&mut i.len, replaces);
+        },
+        Expr::Return(i) => {
+            if let Some(e) = &mut i.expr {
+                replace_item_name_in_expr(e, replaces);
+            }
+        },
+        Expr::Struct(i) => {
+            if let Some(qself) = &mut i.qself {
+                replace_item_name_in_type(&mut qself.ty, replaces);
+            }
+            replace_item_name_in_path(&mut i.path, replaces);
+            for f in &mut i.fields {
+                replace_item_name_in_expr(&mut f.expr, replaces);
+            }
+            if let Some(r) = &mut i.rest {
+                replace_item_name_in_expr(r, replaces);
+            }
+        },
+        Expr::Try(i) => replace_item_name_in_expr(&mut i.expr, replaces),
+        Expr::TryBlock(i) => replace_item_name_in_block(&mut i.block, replaces),
+        Expr::Tuple(i) => {
+            for e in &mut i.elems {
+                replace_item_name_in_expr(e, replaces);
+            }
+        },
+        Expr::Unary(i) => replace_item_name_in_expr(&mut i.expr, replaces),
+        Expr::Unsafe(i) => replace_item_name_in_block(&mut i.block, replaces),
+        Expr::While(i) => {
+            replace_item_name_in_expr(&mut i.cond, replaces);
+            replace_item_name_in_block(&mut i.body, replaces);
+        },
+        Expr::Yield(i) => {
+            if let Some(e) = &mut i.expr {
+                replace_item_name_in_expr(e, replaces);
+            }
+        },
+        Expr::Verbatim(_) => (),
+        Expr::Lit(_) => (),
+        Expr::Infer(_) => (),
+        Expr::Continue(_) => (),
+        _ => (),
+    }
 }
 
 pub fn replace_item_name_in_generics<'a>(
@@ -168,7 +422,7 @@ pub fn replace_item_name_in_generics<'a>(
 
 pub fn replace_item_name_in_type<'a>(ty: &mut Type, replaces: &[ReplaceItemNameFromTo<'a>]) {
     match ty {
-        Type::Array(type_array) => replace_item_name_in_type(&mut *type_array.elem, replaces),
+        Type::Array(type_array) => replace_item_name_in_type(&mut type_array.elem, replaces),
         Type::BareFn(type_bare_fn) => {
             for input in &mut type_bare_fn.inputs {
                 replace_item_name_in_type(&mut input.ty, replaces);
@@ -184,15 +438,20 @@ pub fn replace_item_name_in_type<'a>(ty: &mut Type, replaces: &[ReplaceItemNameF
         Type::Paren(type_paren) => replace_item_name_in_type(&mut type_paren.elem, replaces),
         Type::Path(type_path) => {
             if let Some(qself) = &mut type_path.qself {
-                replace_item_name_in_type(&mut *qself.ty, replaces);
+                replace_item_name_in_type(&mut qself.ty, replaces);
+            }
+            // Lock<T> のように型全体が別名の場合は、パスで表せない型 ([u8; 4] など) にも置き換えられる
+            else if let Some(new_ty) = replace_type_alias_in_path(&mut type_path.path, replaces) {
+                *ty = new_ty;
+                return;
             }
             replace_item_name_in_path(&mut type_path.path, replaces)
         }
-        Type::Ptr(type_ptr) => replace_item_name_in_type(&mut *type_ptr.elem, replaces),
+        Type::Ptr(type_ptr) => replace_item_name_in_type(&mut type_ptr.elem, replaces),
         Type::Reference(type_reference) => {
-            replace_item_name_in_type(&mut *type_reference.elem, replaces)
+            replace_item_name_in_type(&mut type_reference.elem, replaces)
         }
-        Type::Slice(type_slice) => replace_item_name_in_type(&mut *type_slice.elem, replaces),
+        Type::Slice(type_slice) => replace_item_name_in_type(&mut type_slice.elem, replaces),
         Type::TraitObject(type_trait_object) => {
             for bound in &mut type_trait_object.bounds {
                 replace_item_name_in_type_param_bound(bound, replaces);
@@ -218,36 +477,171 @@ pub fn replace_item_name_in_field<'a>(f: &mut Field, replaces: &[ReplaceItemName
 // (from, to)
 // (std, s): std::io::Read -> s::io::Read
 // (B, std::io::BufReader): B<T> -> std::io::BufReader<T>
+// (tokio::fs, std::fs): tokio::fs::read -> std::fs::read
+// (Lock<T>, tokio::sync::RwLock<T>): Lock::<u8>::new -> tokio::sync::RwLock::<u8>::new
 pub fn replace_item_name_in_path<'a>(path: &mut Path, replaces: &[ReplaceItemNameFromTo<'a>]) {
     for seg in &mut path.segments {
         replace_item_name_in_path_arguments(&mut seg.arguments, replaces);
     }
 
-    if let Some(root_seg) = path.segments.get_mut(0) {
-        for ReplaceItemNameFromTo { from, to } in replaces.iter() {
-            if from.as_ref() == &root_seg.ident {
-                let mut seg_buf = Punctuated::new();
+    for ReplaceItemNameFromTo { from, to } in replaces.iter() {
+        let n = from.segments.len();
+        if !is_prefix_matched(path, from) {
+            continue;
+        }
+
+        let last_seg = &path.segments[n - 1];
+        let mut seg_buf = Punctuated::new();
+        let leading_colon = match to {
+            ReplaceItemNameTo::Path(to) => {
                 for ident in &to.segments {
                     seg_buf.push(PathSegment {
                         ident: ident.clone(),
                         arguments: PathArguments::None,
                     });
                 }
-                seg_buf.last_mut().map(|s| {
-                    s.ident.set_span(root_seg.ident.span());
-                    s.arguments = root_seg.arguments.clone();
-                });
-
-                for seg in path.segments.iter().skip(1) {
-                    seg_buf.push(seg.clone());
+                if let Some(s) = seg_buf.last_mut() {
+                    s.ident.set_span(last_seg.ident.span());
+                    s.arguments = last_seg.arguments.clone();
+                }
+                to.leading_colon
+            },
+            ReplaceItemNameTo::Type(alias) => {
+                // パスの途中に置くので、パスで表せる型でなければ置き換えられない
+                let Some(Type::Path(TypePath { qself: None, path: to })) = 
+                    substitute_type_alias(alias, &last_seg.arguments) else {
+                    continue;
+                };
+                seg_buf = to.segments;
+                // 式の中でも使えるようにターボフィッシュにする
+                if path.segments.len() > n {
+                    if let Some(PathArguments::AngleBracketed(a)) = seg_buf.last_mut().map(|s| &mut s.arguments) {
+                        a.colon2_token = Some(Default::default());
+                    }
                 }
+                to.leading_colon
+            },
+        };
 
-                path.leading_colon = to.leading_colon;
-                path.segments = seg_buf;
-                return;
-            }
+        for seg in path.segments.iter().skip(n) {
+            seg_buf.push(seg.clone());
         }
+
+        path.leading_colon = leading_colon;
+        path.segments = seg_buf;
+        return;
+    }
+}
+
+fn is_prefix_matched(path: &Path, from: &PathWithoutArgs) -> bool {
+    let n = from.segments.len();
+    if n == 0 || path.segments.len() < n {
+        return false;
+    }
+    if from.leading_colon.is_some() && path.leading_colon.is_none() {
+        return false;
     }
+
+    from.segments
+        .iter()
+        .zip(path.segments.iter())
+        .all(|(f, seg)| f == &seg.ident)
+}
+
+/// path が型の別名そのもの (Lock<T> など) であれば、置き換え後の型を返す。
+/// 最初に一致したものが別名でなければ None
+fn replace_type_alias_in_path<'a>(path: &mut Path, replaces: &[ReplaceItemNameFromTo<'a>]) -> Option<Type> {
+    if path.leading_colon.is_some() || path.segments.len() != 1 {
+        return None;
+    }
+
+    let replace = replaces.iter().find(|r| is_prefix_matched(path, &r.from))?;
+    let ReplaceItemNameTo::Type(alias) = &replace.to else {
+        return None;
+    };
+
+    let seg = &mut path.segments[0];
+    replace_item_name_in_path_arguments(&mut seg.arguments, replaces);
+    substitute_type_alias(alias, &seg.arguments)
+}
+
+/// alias.ty のジェネリクスの引数を args で位置ごとに置き換える。
+/// 引数が足りない場合は既定の型か _ や '_ で埋める。
+fn substitute_type_alias(alias: &TypeAlias, args: &PathArguments) -> Option<Type> {
+    let mut given = match args {
+        PathArguments::AngleBracketed(a) => a.args.iter().collect::<Vec<_>>(),
+        _ => Vec::new(),
+    }
+    .into_iter();
+
+    let mut types = HashMap::new();
+    let mut lifetimes = HashMap::new();
+    for param in &alias.generics.params {
+        let arg = given.next().map(|a| a.to_token_stream());
+        match param {
+            GenericParam::Lifetime(p) => {
+                let arg = arg.unwrap_or_else(|| quote!('_));
+                lifetimes.insert(p.lifetime.ident.to_string(), arg);
+            },
+            GenericParam::Type(p) => {
+                let arg = arg
+                    .or_else(|| p.default.as_ref().map(|d| d.to_token_stream()))
+                    .unwrap_or_else(|| quote!(_));
+                types.insert(p.ident.to_string(), arg);
+            },
+            GenericParam::Const(p) => {
+                let arg = arg
+                    .or_else(|| p.default.as_ref().map(|d| d.to_token_stream()))
+                    .unwrap_or_else(|| quote!(_));
+                types.insert(p.ident.to_string(), arg);
+            },
+        }
+    }
+
+    let tokens = substitute_tokens(alias.ty.to_token_stream(), &types, &lifetimes);
+    syn::parse2(tokens).ok()
+}
+
+fn substitute_tokens(
+    tokens: TokenStream,
+    types: &HashMap<String, TokenStream>,
+    lifetimes: &HashMap<String, TokenStream>,
+) -> TokenStream {
+
+    let mut buf = TokenStream::new();
+    let mut iter = tokens.into_iter().peekable();
+
+    while let Some(tt) = iter.next() {
+        match tt {
+            TokenTree::Group(g) => {
+                let mut new_g = Group::new(g.delimiter(), substitute_tokens(g.stream(), types, lifetimes));
+                new_g.set_span(g.span());
+                buf.extend([TokenTree::Group(new_g)]);
+            },
+            // 'a は ' と a に分かれている
+            TokenTree::Punct(p) if p.as_char() == '\'' && p.spacing() == Spacing::Joint => {
+                let lifetime = match iter.peek() {
+                    Some(TokenTree::Ident(i)) => lifetimes.get(&i.to_string()),
+                    _ => None,
+                };
+                match lifetime {
+                    Some(l) => {
+                        iter.next();
+                        buf.extend(l.clone());
+                    },
+                    None => buf.extend([TokenTree::Punct(p)]),
+                }
+            },
+            // 周囲の型と結合しないようにグループで包む (&T に dyn A + B を入れる場合など)
+            TokenTree::Ident(i) if types.contains_key(&i.to_string()) => {
+                let g = Group::new(Delimiter::None, types[&i.to_string()].clone());
+                buf.extend([TokenTree::Group(g)]);
+            },
+            tt => buf.extend([tt]),
+        }
+    }
+
+    buf
 }
 
 pub fn replace_item_name_in_return_type<'a>(
@@ -29,55 +29,62 @@ pub fn replace_item_name_in_doc<'a, 'b>(
         // buf は start の大きい順に pop することでインデックスズレを防ぐ
         while let Some(i) = buf.pop() {
             let item_path = &doc[i.inner.clone()];
-            let (item_name, rest) = item_path.split_once("::").unwrap_or((item_path, ""));
             
-            // replaces に一致する場合に置換
+            // replaces に前方一致する場合に置換
             for replace in replaces {
                 let from = replace.from.to_string();
 
-                let f = from.split_once("::").map(|(s, _)| s).unwrap_or(&from);
-                if f == item_name {
-                    // [Type](SomeType)
-                    // [`Type`][SomeType]
-                    //
-                    // (from, to) = (F, T)
-                    // [Type](F) => [Type](T)
-                    // [Type](F::A) => [Type](T::A)
-                    if i.has_label {
-                        let mut to = replace.to.to_string();
-                        if !rest.is_empty() {
-                            to.push_str("::");
-                            to.push_str(rest);
-                        }
-                        new_doc.replace_range(i.inner.clone(), &to);
-                    }
-                    // [Type]
-                    // [`Type`]
-                    // 
-                    // (from, to) = (F, T)
-                    // [F] => [F](T)
-                    // [`F()`] => [`F()`](`T()`)
-                    else {
-                        let prefix = &doc[i.outer.start..i.inner.start];
-                        let suffix = &doc[i.inner.end..i.outer.end];
-                        let mut to = String::new();
-                        to.push('(');
-                        to.push_str(prefix);
-                        to.push_str(&replace.to.to_string());
-                        if !rest.is_empty() {
-                            to.push_str("::");
-                            to.push_str(rest);
-                        }
-                        to.push_str(suffix);
-                        to.push(')');
-                        
-                        // outer.end + 1 の位置には ] が必ず存在する
-                        let t = i.outer.end + 1;
-                        new_doc.replace_range(t..t, &to);
-                    }
+                let rest = match item_path.strip_prefix(from.as_str()) {
+                    Some(rest) if rest.is_empty() => rest,
+                    Some(rest) if rest.starts_with("::") => &rest[2..],
+                    _ => continue,
+                };
 
+                // パスで表せない型の別名 ([u8; 4] など) はリンクにできない
+                let Some(replace_to) = replace.to.to_path_string() else {
                     break;
+                };
+
+                // [Type](SomeType)
+                // [`Type`][SomeType]
+                //
+                // (from, to) = (F, T)
+                // [Type](F) => [Type](T)
+                // [Type](F::A) => [Type](T::A)
+                if i.has_label {
+                    let mut to = replace_to;
+                    if !rest.is_empty() {
+                        to.push_str("::");
+                        to.push_str(rest);
+                    }
+                    new_doc.replace_range(i.inner.clone(), &to);
+                }
+                // [Type]
+                // [`Type`]
+                // 
+                // (from, to) = (F, T)
+                // [F] => [F](T)
+                // [`F()`] => [`F()`](`T()`)
+                else {
+                    let prefix = &doc[i.outer.start..i.inner.start];
+                    let suffix = &doc[i.inner.end..i.outer.end];
+                    let mut to = String::new();
+                    to.push('(');
+                    to.push_str(prefix);
+                    to.push_str(&replace_to);
+                    if !rest.is_empty() {
+                        to.push_str("::");
+                        to.push_str(rest);
+                    }
+                    to.push_str(suffix);
+                    to.push(')');
+                    
+                    // outer.end + 1 の位置には ] が必ず存在する
+                    let t = i.outer.end + 1;
+                    new_doc.replace_range(t..t, &to);
                 }
+
+                break;
             }
         }
 
@@ -1,11 +1,13 @@
-use crate::ItemImport;
+use crate::{ItemImport, TypeAlias, VariantArgs};
 use proc_macro2::TokenStream;
 use quote::ToTokens;
 use syn::{
+    ext::IdentExt,
     parse::Parse,
     punctuated::Punctuated,
     token::{self, PathSep},
-    Ident, ItemUse, Path, PathArguments, PathSegment, Token, UseName, UsePath, UseRename, UseTree,
+    parse_quote, Attribute, Error, Expr, Ident, Item, ItemType, ItemUse, LitStr, Macro, Path, PathArguments, PathSegment, 
+    Result, Token, UseGlob, UseName, UsePath, UseRename, UseTree, Visibility,
 };
 
 
@@ -13,11 +15,11 @@ pub fn item_use_from_item_import(item_import: &ItemImport) -> ItemUse {
     let use_tree = {
         let mut iter = item_import.path.segments.iter().rev();
 
-        let top_ident = iter.next().unwrap().clone();
-        let mut tree = match &item_import.alias {
-            None => UseTree::Name(UseName { ident: top_ident }),
-            Some(alias) => UseTree::Rename(UseRename {
-                ident: top_ident,
+        let mut tree = match (item_import.is_glob, &item_import.alias) {
+            (true, _) => UseTree::Glob(UseGlob { star_token: Default::default() }),
+            (false, None) => UseTree::Name(UseName { ident: iter.next().unwrap().clone() }),
+            (false, Some(alias)) => UseTree::Rename(UseRename {
+                ident: iter.next().unwrap().clone(),
                 as_token: Default::default(),
                 rename: alias.clone(),
             })
@@ -35,15 +37,174 @@ pub fn item_use_from_item_import(item_import: &ItemImport) -> ItemUse {
     };
 
     ItemUse {
-        attrs: Vec::new(),
+        // 注入した use が使われなくても警告が出ないようにする
+        attrs: vec![parse_quote! { #[allow(unused_imports)] }],
         vis: syn::Visibility::Inherited,
         use_token: Default::default(),
-        leading_colon: item_import.path.leading_colon.clone(),
+        leading_colon: item_import.path.leading_colon,
         tree: use_tree,
         semi_token: Default::default(),
     }
 }
 
+/// 項目の名前の書き方
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum NameCase {
+    /// 型やトレイト (Foo)
+    UpperCamel,
+    /// 関数やモジュール (foo)
+    Snake,
+    /// 定数や静的変数 (FOO)
+    ScreamingSnake,
+}
+
+impl NameCase {
+
+    /// 名前の書き方から判断する
+    /// 
+    /// Foo -> UpperCamel
+    /// foo -> Snake
+    /// FOO -> ScreamingSnake
+    pub fn of(ident: &Ident) -> NameCase {
+        let s = ident.unraw().to_string();
+        let is_upper_camel = s.starts_with(|c: char| c.is_ascii_uppercase());
+        let is_screaming = s.chars().any(|c| c.is_ascii_alphabetic()) 
+            && s.chars().all(|c| !c.is_ascii_lowercase());
+
+        if is_screaming && s.len() > 1 {
+            NameCase::ScreamingSnake
+        }
+        else if is_upper_camel {
+            NameCase::UpperCamel
+        }
+        else {
+            NameCase::Snake
+        }
+    }
+
+    /// 生成される名前の既定の書式
+    pub fn default_templates(self) -> (&'static str, &'static str) {
+        match self {
+            NameCase::UpperCamel => ("Sync{}", "Async{}"),
+            NameCase::Snake => ("sync_{}", "async_{}"),
+            NameCase::ScreamingSnake => ("SYNC_{}", "ASYNC_{}"),
+        }
+    }
+
+    /// 書式がこの書き方の名前を作るか確かめる。
+    /// {} にこの書き方の名前を入れて、結果も同じ書き方になるかを見る
+    pub fn check_template(self, template: &LitStr) -> Result<()> {
+        let (sample, name) = match self {
+            NameCase::UpperCamel => ("Name", "UpperCamelCase"),
+            NameCase::Snake => ("name", "snake_case"),
+            NameCase::ScreamingSnake => ("NAME", "SCREAMING_SNAKE_CASE"),
+        };
+        let formatted = format_name(&template.value(), sample);
+        let is_matched = match self {
+            NameCase::UpperCamel => formatted.starts_with(|c: char| c.is_ascii_uppercase()) && !formatted.contains('_'),
+            NameCase::Snake => !formatted.chars().any(|c| c.is_ascii_uppercase()),
+            NameCase::ScreamingSnake => !formatted.chars().any(|c| c.is_ascii_lowercase()),
+        };
+
+        match is_matched {
+            true => Ok(()),
+            false => Err(Error::new(
+                template.span(), 
+                format!("this item needs a {name} name template such as \"{}\"", self.default_templates().0)
+            )),
+        }
+    }
+}
+
+/// マクロの中身は任意のトークン列なので、カンマ区切りの式として解釈できる場合のみ、それぞれの式に f を適用する。
+/// 解釈できなければ何もせず false を返す。トークン列は変わった場合だけ置き換える (スパンを残すため)
+pub fn map_macro_exprs(mac: &mut Macro, mut f: impl FnMut(&mut Expr) -> Result<()>) -> Result<bool> {
+    let Ok(mut exprs) = mac.parse_body_with(Punctuated::<Expr, Token![,]>::parse_terminated) else {
+        return Ok(false);
+    };
+
+    for expr in &mut exprs {
+        f(expr)?;
+    }
+
+    let tokens = exprs.into_token_stream();
+    if tokens.to_string() != mac.tokens.to_string() {
+        mac.tokens = tokens;
+    }
+    Ok(true)
+}
+
+/// 書式の {} を name で置き換える
+pub fn format_name(template: &str, name: &str) -> String {
+    template.replace("{}", name)
+}
+
+pub fn format_ident_with_template(template: &str, ident: &Ident) -> Ident {
+    if template == "{}" {
+        return ident.clone();
+    }
+
+    let name = format_name(template, &ident.unraw().to_string());
+    let mut new_ident = syn::parse_str::<Ident>(&name)
+        .unwrap_or_else(|_| Ident::new_raw(&name, ident.span()));
+    new_ident.set_span(ident.span());
+    new_ident
+}
+
+/// sync_name, async_name の指定がなければ case の既定の書式を使う。
+/// 指定された書式が case に合わない場合はエラーにする
+pub fn variant_templates(
+    case: NameCase,
+    args_for_sync: &VariantArgs,
+    args_for_async: &VariantArgs,
+) -> Result<(String, String)> {
+
+    let (default_sync, default_async) = case.default_templates();
+    let template = |name: &Option<LitStr>, default: &str| match name {
+        Some(lit) => case.check_template(lit).map(|_| lit.value()),
+        None => Ok(default.to_string()),
+    };
+    Ok((template(&args_for_sync.name, default_sync)?, template(&args_for_async.name, default_async)?))
+}
+
+/// 生成される sync, async の名前
+pub fn variant_idents(
+    ident: &Ident,
+    case: NameCase,
+    args_for_sync: &VariantArgs,
+    args_for_async: &VariantArgs,
+) -> Result<(Ident, Ident)> {
+
+    let (sync_template, async_template) = variant_templates(case, args_for_sync, args_for_async)?;
+    Ok((format_ident_with_template(&sync_template, ident), format_ident_with_template(&async_template, ident)))
+}
+
+/// 生成される sync, async の可視性。
+/// sync_vis, async_vis の指定がなければ元の可視性を使う
+pub fn variant_vis(
+    vis: &Visibility,
+    args_for_sync: &VariantArgs,
+    args_for_async: &VariantArgs,
+) -> (Visibility, Visibility) {
+
+    let sync_vis = args_for_sync.vis.clone().unwrap_or_else(|| vis.clone());
+    let async_vis = args_for_async.vis.clone().unwrap_or_else(|| vis.clone());
+    (sync_vis, async_vis)
+}
+
+/// 生成される項目に付ける #[cfg(...)] と #[cfg_attr(docsrs, doc(cfg(...)))]
+pub fn cfg_attrs_from_variant_args(args: &VariantArgs) -> Vec<Attribute> {
+    let Some(cfg) = &args.cfg else {
+        return Vec::new();
+    };
+
+    let mut buf: Vec<Attribute> = vec![parse_quote! { #[cfg(#cfg)] }];
+    if args.doc_cfg {
+        buf.push(parse_quote! { #[cfg_attr(docsrs, doc(cfg(#cfg)))] });
+    }
+    buf
+}
+
 pub fn item_uses_from_item_imports(item_imports: &[ItemImport]) -> Vec<ItemUse> {
     let mut buf = Vec::with_capacity(item_imports.len());
     for item_import in item_imports {
@@ -52,6 +213,27 @@ pub fn item_uses_from_item_imports(item_imports: &[ItemImport]) -> Vec<ItemUse>
     buf
 }
 
+pub fn item_type_from_type_alias(type_alias: &TypeAlias) -> ItemType {
+    let TypeAlias { ident, generics, ty } = type_alias;
+    // 使われない別名で警告が出ないようにする
+    parse_quote! {
+        #[allow(dead_code)]
+        type #ident #generics = #ty;
+    }
+}
+
+/// 本体やモジュールの先頭に注入する use と type
+pub fn items_from_variant_args(args: &VariantArgs) -> Vec<Item> {
+    let mut buf = Vec::with_capacity(args.item_imports.len() + args.type_aliases.len());
+    for item_use in item_uses_from_item_imports(&args.item_imports) {
+        buf.push(Item::Use(item_use));
+    }
+    for type_alias in &args.type_aliases {
+        buf.push(Item::Type(item_type_from_type_alias(type_alias)));
+    }
+    buf
+}
+
 /// Arguments (std::io::BufReader<'a, T> の 'a, T など) がないパス
 #[derive(Clone)]
 pub struct PathWithoutArgs {
@@ -61,20 +243,6 @@ pub struct PathWithoutArgs {
 
 impl PathWithoutArgs {
 
-    pub fn to_string(&self) -> String {
-        let mut buf = String::new();
-        if self.leading_colon.is_some() {
-            buf.push_str("::");
-        }
-        for (i, seg) in self.segments.iter().enumerate() {
-            if i != 0 {
-                buf.push_str("::");
-            }
-            buf.push_str(&seg.to_string());
-        }
-        buf
-    }
-
     pub fn from_idents(idents: Vec<Ident>, is_absolute: bool) -> PathWithoutArgs {
         let mut segments = Punctuated::new();
 
@@ -94,6 +262,33 @@ impl PathWithoutArgs {
     pub fn last_ident(&self) -> Option<&Ident> {
         self.segments.last()
     }
+
+    pub fn is_ident(&self, ident: &Ident) -> bool {
+        self.leading_colon.is_none() && self.segments.len() == 1 && &self.segments[0] == ident
+    }
+
+    /// Self::ident かどうか。self.ident() の呼び出しの判定に使う
+    pub fn is_self_item(&self, ident: &Ident) -> bool {
+        self.leading_colon.is_none() 
+            && self.segments.len() == 2 
+            && self.segments[0] == "Self" 
+            && &self.segments[1] == ident
+    }
+}
+
+impl std::fmt::Display for PathWithoutArgs {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.leading_colon.is_some() {
+            f.write_str("::")?;
+        }
+        for (i, seg) in self.segments.iter().enumerate() {
+            if i != 0 {
+                f.write_str("::")?;
+            }
+            write!(f, "{seg}")?;
+        }
+        Ok(())
+    }
 }
 
 impl From<PathWithoutArgs> for Path {
@@ -140,12 +335,13 @@ impl Parse for PathWithoutArgs {
         let mut segments = Punctuated::<Ident, Token![::]>::new();
 
         // 少なくとも1つは Ident が必要
-        let first: Ident = input.parse()?;
+        // crate, self, super もパスの先頭になり得るので parse_any を使う
+        let first = Ident::parse_any(input)?;
         segments.push(first);
 
         while input.peek(Token![::]) {
             let _colon: Token![::] = input.parse()?;
-            let ident: Ident = input.parse()?;
+            let ident = Ident::parse_any(input)?;
             segments.push(ident);
         }
 
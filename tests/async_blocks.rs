@@ -0,0 +1,68 @@
+mod common;
+
+use common::block_on;
+use sync_async::sync_async;
+
+
+pub fn spawn<F: std::future::Future>(f: F) -> F {
+    f
+}
+
+#[sync_async]
+pub mod blocks {
+    #[maybe_async]
+    pub fn double(n: u32) -> u32 {
+        n * 2
+    }
+
+    // sync では async ブロックはすぐに評価されるブロックになる
+    #[maybe_async]
+    pub fn block(n: u32) -> u32 {
+        let f = async move { double(n).await };
+        f.await + 1
+    }
+
+    // return や ? を含む場合はすぐに呼ばれるクロージャになる
+    #[maybe_async]
+    pub fn early_return(s: &str) -> Result<u32, std::num::ParseIntError> {
+        let f = async move {
+            let n: u32 = s.parse()?;
+            if n == 0 {
+                return Ok(0);
+            }
+            Ok(double(n).await)
+        };
+        f.await
+    }
+
+    // async クロージャは普通のクロージャになる
+    #[maybe_async]
+    pub fn closure(n: u32) -> u32 {
+        let f = async |x: u32| double(x).await;
+        f(n).await + f(1).await
+    }
+
+    // always_async の関数は sync でも async fn なので、async ブロックはそのまま残る
+    #[always_async]
+    pub fn spawned(n: u32) -> u32 {
+        crate::spawn(async move { n * 2 + 1 }).await
+    }
+}
+
+#[test]
+fn sync_async_blocks() {
+    assert_eq!(sync_blocks::block(2), 5);
+    assert_eq!(sync_blocks::early_return("3"), Ok(6));
+    assert_eq!(sync_blocks::early_return("0"), Ok(0));
+    assert!(sync_blocks::early_return("x").is_err());
+    assert_eq!(sync_blocks::closure(2), 6);
+    assert_eq!(block_on(sync_blocks::spawned(2)), 5);
+}
+
+#[test]
+fn async_async_blocks() {
+    assert_eq!(block_on(async_blocks::block(2)), 5);
+    assert_eq!(block_on(async_blocks::early_return("3")), Ok(6));
+    assert_eq!(block_on(async_blocks::closure(2)), 6);
+    assert_eq!(block_on(async_blocks::spawned(2)), 5);
+}
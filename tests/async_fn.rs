@@ -0,0 +1,44 @@
+mod common;
+
+use common::block_on;
+use sync_async::sync_async;
+
+
+#[sync_async]
+pub struct S;
+
+// async fn は maybe_async として扱われ、sync のバリアントでは async と .await が外れる
+#[sync_async(async_fn = maybe)]
+impl S {
+    pub async fn a(&self) -> u8 { 1 }
+
+    pub async fn b(&self) -> u8 { self.a().await + 1 }
+
+    #[always_async]
+    pub async fn c(&self) -> u8 { 3 }
+
+    #[always_sync]
+    pub fn d(&self) -> u8 { 4 }
+}
+
+#[sync_async(async_fn = maybe)]
+pub mod m {
+    pub async fn one() -> u8 { 1 }
+
+    pub async fn two() -> u8 { one().await + one().await }
+}
+
+#[test]
+fn sync_async_fn() {
+    assert_eq!(SyncS.b(), 2);
+    assert_eq!(block_on(SyncS.c()), 3);
+    assert_eq!(SyncS.d(), 4);
+    assert_eq!(sync_m::two(), 2);
+}
+
+#[test]
+fn async_async_fn() {
+    assert_eq!(block_on(AsyncS.b()), 2);
+    assert_eq!(block_on(AsyncS.c()), 3);
+    assert_eq!(block_on(async_m::two()), 2);
+}
@@ -0,0 +1,80 @@
+mod common;
+
+use common::block_on;
+use sync_async::sync_async;
+
+
+#[sync_async]
+pub struct S;
+
+#[sync_async(auto_await)]
+impl S {
+    #[maybe_async]
+    pub fn a(&self) -> u8 { 1 }
+
+    #[always_async]
+    pub fn b(&self) -> u8 { 2 }
+
+    // すでに .await が付いている呼び出しはそのまま
+    #[maybe_async(use std::convert::identity)]
+    pub fn c(&self) -> u8 { identity(self.a() + self.a().await) }
+
+    #[always_sync]
+    pub fn d(&self) -> u8 { 4 }
+
+    #[maybe_async]
+    pub fn e(&self) -> u8 { self.d() + { let f = || 1; f() } }
+
+    #[maybe_async]
+    pub fn g(&self) -> u8 { assert_eq!(Self::a(self), 1); self.a() + 1 }
+}
+
+pub fn get(x: u8) -> u8 {
+    x
+}
+
+#[sync_async]
+pub struct G;
+
+// impl の中の get() は外の関数の呼び出しなので .await を付けない
+#[sync_async(auto_await)]
+impl G {
+    #[maybe_async]
+    pub fn get(&self) -> u8 { 1 }
+
+    #[maybe_async]
+    pub fn sum(&self) -> u8 { get(3) + self.get() + Self::get(self) + G::get(self) }
+}
+
+#[sync_async(auto_await, default = maybe_async)]
+pub mod m {
+    pub fn one() -> u8 { 1 }
+
+    pub fn two() -> u8 { one() + one() }
+
+    pub struct T;
+
+    // ネストした impl からも外側のモジュールの関数に .await が付く
+    impl T {
+        pub fn three(&self) -> u8 { one() + two() }
+    }
+}
+
+#[test]
+fn sync_auto_await() {
+    assert_eq!(SyncS.c(), 2);
+    assert_eq!(SyncS.e(), 5);
+    assert_eq!(sync_m::two(), 2);
+    assert_eq!(sync_m::T.three(), 3);
+    assert_eq!(SyncG.sum(), 6);
+}
+
+#[test]
+fn async_auto_await() {
+    assert_eq!(block_on(AsyncS.c()), 2);
+    assert_eq!(block_on(AsyncS.e()), 5);
+    assert_eq!(block_on(AsyncS.g()), 2);
+    assert_eq!(block_on(async_m::two()), 2);
+    assert_eq!(block_on(async_m::T.three()), 3);
+    assert_eq!(block_on(AsyncG.sum()), 6);
+}
@@ -0,0 +1,38 @@
+use sync_async::sync_async;
+
+
+// cfg_async が偽なので async のバリアントは出力されない
+#[sync_async(cfg_sync = all(), cfg_async = any(), doc_cfg)]
+pub struct C;
+
+#[sync_async(cfg_sync = all(), cfg_async = any(), use(if_async) crate::missing::Thing)]
+impl C {
+    #[maybe_async]
+    pub fn f(&self) -> u8 {
+        #[if_async] let _: Thing;
+        1
+    }
+}
+
+#[sync_async(cfg_sync = all(), cfg_async = any())]
+pub mod m {
+    #[maybe_async]
+    pub fn g() -> u8 { 2 }
+}
+
+#[sync_async(cfg_sync = any())]
+pub const N: u8 = 3;
+
+// 同じ名前を定義しても衝突しない
+pub struct AsyncC;
+
+pub mod async_m {}
+
+pub const SYNC_N: u8 = 4;
+
+#[test]
+fn cfg_gating() {
+    assert_eq!(SyncC.f() + sync_m::g(), 3);
+    assert_eq!(ASYNC_N + SYNC_N, 7);
+    let _ = AsyncC;
+}
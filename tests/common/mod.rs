@@ -0,0 +1,13 @@
+use std::{future::Future, pin::pin, task::{Context, Poll, Waker}};
+
+
+/// テスト用の最小の executor。テストの future は待機しないので、ポーリングを繰り返すだけでいい
+pub fn block_on<F: Future>(f: F) -> F::Output {
+    let mut f = pin!(f);
+    let mut cx = Context::from_waker(Waker::noop());
+    loop {
+        if let Poll::Ready(v) = f.as_mut().poll(&mut cx) {
+            return v;
+        }
+    }
+}
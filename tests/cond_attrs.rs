@@ -0,0 +1,72 @@
+mod common;
+
+use common::block_on;
+use sync_async::sync_async;
+
+
+#[sync_async]
+#[if_sync(derive(Clone, Debug))]
+#[if_async(derive(Debug))]
+pub struct S {
+    #[if_sync(doc = "sync field")]
+    pub n: u8,
+}
+
+#[sync_async]
+#[if_async(allow(dead_code))]
+impl S {
+    #[maybe_async]
+    #[if_async(must_use)]
+    pub fn n(&self) -> u8 {
+        self.n
+    }
+}
+
+#[sync_async]
+pub mod m {
+    #[if_sync(derive(Clone))]
+    pub struct T(#[if_async(allow(unused))] pub u8);
+
+    // 印のない関数や const なども属性を選ぶ
+    impl T {
+        #[if_async(cfg(any()))]
+        pub fn only_sync(&self) -> u8 {
+            self.0
+        }
+
+        #[if_sync(cfg(any()))]
+        pub fn only_async(&self) -> u8 {
+            self.0 + 1
+        }
+    }
+
+    #[if_async(cfg(any()))]
+    pub const ONLY_SYNC: u8 = 1;
+
+    #[if_sync(cfg(any()))]
+    pub static ONLY_ASYNC: u8 = 2;
+
+    #[if_sync(test)]
+    #[maybe_async]
+    fn sync_only_test() {
+        assert_eq!(T(1).0, 1)
+    }
+}
+
+#[test]
+fn sync_cond_attrs() {
+    let s = SyncS { n: 1 }.clone();
+    assert_eq!(s.n(), 1);
+    let t = sync_m::T(1).clone();
+    assert_eq!(t.only_sync(), 1);
+    assert_eq!(sync_m::ONLY_SYNC, 1);
+}
+
+#[test]
+fn async_cond_attrs() {
+    let s = AsyncS { n: 1 };
+    assert_eq!(format!("{s:?}"), "AsyncS { n: 1 }");
+    assert_eq!(block_on(s.n()), 1);
+    assert_eq!(async_m::T(1).only_async(), 2);
+    assert_eq!(async_m::ONLY_ASYNC, 2);
+}
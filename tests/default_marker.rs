@@ -0,0 +1,39 @@
+mod common;
+
+use common::block_on;
+use sync_async::sync_async;
+
+
+#[sync_async]
+pub struct S;
+
+// 印のない関数は default の印になる。明示した印が優先する
+#[sync_async(default = maybe_async)]
+impl S {
+    pub fn a(&self) -> u8 { 1 }
+
+    pub fn b(&self) -> u8 { self.a().await + 1 }
+
+    #[always_sync]
+    pub fn c(&self) -> u8 { 3 }
+}
+
+#[sync_async(default = always_sync)]
+pub mod m {
+    pub fn helper() -> u8 { 1 }
+
+    #[maybe_async]
+    pub fn f() -> u8 { helper() + 1 }
+}
+
+#[test]
+fn sync_default_marker() {
+    assert_eq!(SyncS.b() + SyncS.c(), 5);
+    assert_eq!(sync_m::helper() + sync_m::f(), 3);
+}
+
+#[test]
+fn async_default_marker() {
+    assert_eq!(block_on(AsyncS.b()) + AsyncS.c(), 5);
+    assert_eq!(async_m::helper() + block_on(async_m::f()), 3);
+}
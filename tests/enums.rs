@@ -0,0 +1,47 @@
+use sync_async::sync_async;
+
+
+#[sync_async]
+pub struct Conn(pub u8);
+
+#[sync_async]
+#[derive(Debug, PartialEq)]
+pub enum Code {
+    Ok = 0,
+    Busy = 3,
+}
+
+/// [Conn] のイベント
+#[sync_async(use(if_sync) crate::SyncConn as Conn, use(if_async) crate::AsyncConn as Conn)]
+pub enum Event<T> {
+    /// [Conn] を開いた
+    Open(Conn),
+    Data { conn: Conn, body: T },
+    Close,
+}
+
+#[sync_async]
+impl Event<u8> {
+    #[always_sync]
+    pub fn conn_id(&self) -> Option<u8> {
+        match self {
+            Self::Open(c) | Self::Data { conn: c, .. } => Some(c.0),
+            Self::Close => None,
+        }
+    }
+}
+
+#[test]
+fn enum_discriminants() {
+    assert_eq!(SyncCode::Busy as u8, 3);
+    assert_eq!(AsyncCode::Ok as u8, 0);
+    assert_eq!(SyncCode::Ok, SyncCode::Ok);
+}
+
+#[test]
+fn enum_payloads() {
+    let e = SyncEvent::Data { conn: SyncConn(1), body: 2u8 };
+    assert_eq!(e.conn_id(), Some(1));
+    assert_eq!(AsyncEvent::<u8>::Open(AsyncConn(2)).conn_id(), Some(2));
+    assert_eq!(AsyncEvent::<u8>::Close.conn_id(), None);
+}
@@ -0,0 +1,58 @@
+mod common;
+
+use common::block_on;
+use sync_async::sync_async;
+
+
+pub struct AsyncMutex<T>(std::sync::Mutex<T>);
+
+impl<T> AsyncMutex<T> {
+    pub fn new(t: T) -> AsyncMutex<T> {
+        AsyncMutex(std::sync::Mutex::new(t))
+    }
+
+    pub async fn lock(&self) -> std::sync::MutexGuard<'_, T> {
+        self.0.lock().unwrap()
+    }
+}
+
+#[sync_async(
+    use(if_sync) std::sync::Mutex,
+    use(if_async) crate::AsyncMutex as Mutex,
+)]
+pub struct Counter {
+    m: Mutex<u32>,
+}
+
+#[sync_async(rewrite(if_sync) .lock().await => .lock().unwrap())]
+impl Counter {
+    #[maybe_async]
+    pub fn incr(&self) -> u32 {
+        let mut g = self.m.lock().await;
+        *g += 1;
+        *g
+    }
+
+    // マクロの引数の中も書き換え、元の式の属性は残す
+    #[maybe_async]
+    pub fn get(&self) -> u32 {
+        assert!(*self.m.lock().await < 100);
+        #[allow(clippy::let_and_return)]
+        let n = *self.m.lock().await;
+        n
+    }
+}
+
+#[test]
+fn sync_rewrite() {
+    let c = SyncCounter { m: std::sync::Mutex::new(0) };
+    assert_eq!(c.incr(), 1);
+    assert_eq!(c.get(), 1);
+}
+
+#[test]
+fn async_rewrite() {
+    let c = AsyncCounter { m: AsyncMutex::new(0) };
+    assert_eq!(block_on(c.incr()), 1);
+    assert_eq!(block_on(c.get()), 1);
+}
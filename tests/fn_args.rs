@@ -0,0 +1,49 @@
+mod common;
+
+use common::block_on;
+use sync_async::sync_async;
+
+
+pub mod foo {
+    pub fn bar() -> &'static str { "foo" }
+    pub struct T;
+}
+
+pub mod baz {
+    pub fn bar() -> &'static str { "baz" }
+    pub struct T;
+}
+
+pub mod qux {
+    pub fn bar() -> &'static str { "qux" }
+}
+
+#[sync_async]
+pub struct S;
+
+#[sync_async(use crate::qux::bar)]
+impl S {
+    // 関数ごとの引数はコンテナの引数より優先する
+    #[maybe_async(use(if_sync) crate::foo::{bar, T}, use(if_async) crate::baz::{bar, T})]
+    pub fn f(&self, _t: T) -> &'static str { bar() }
+
+    #[maybe_async]
+    pub fn g(&self) -> &'static str { bar() }
+
+    #[always_sync(use crate::foo::bar)]
+    pub fn h(&self) -> &'static str { bar() }
+}
+
+#[test]
+fn sync_fn_args() {
+    assert_eq!(SyncS.f(foo::T), "foo");
+    assert_eq!(SyncS.g(), "qux");
+    assert_eq!(SyncS.h(), "foo");
+}
+
+#[test]
+fn async_fn_args() {
+    assert_eq!(block_on(AsyncS.f(baz::T)), "baz");
+    assert_eq!(block_on(AsyncS.g()), "qux");
+    assert_eq!(AsyncS.h(), "foo");
+}
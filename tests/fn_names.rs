@@ -0,0 +1,84 @@
+mod common;
+
+use common::block_on;
+use sync_async::sync_async;
+
+
+#[sync_async]
+pub struct Reader {
+    pub n: u32,
+}
+
+#[sync_async]
+impl Reader {
+    #[maybe_async(sync_name = "{}_blocking")]
+    pub fn read(&self) -> u32 {
+        self.n
+    }
+
+    // 名前が変わる関数の呼び出しも新しい名前になる
+    #[maybe_async]
+    pub fn read_twice(&self) -> u32 {
+        self.read().await + Self::read(self).await
+    }
+
+    #[maybe_async(async_name = "fetch")]
+    pub fn get(&self) -> u32 {
+        self.n
+    }
+}
+
+#[sync_async]
+pub struct Counter {
+    pub n: u32,
+}
+
+#[sync_async(auto_await)]
+impl Counter {
+    #[maybe_async(sync_name = "count_blocking", async_name = "count_async")]
+    pub fn count(&self) -> u32 {
+        self.n
+    }
+
+    // auto_await では新しい名前の呼び出しに .await が付く
+    #[maybe_async]
+    pub fn count_twice(&self) -> u32 {
+        assert!(self.count() > 0);
+        self.count() + Self::count(self)
+    }
+}
+
+#[sync_async]
+pub mod m {
+    #[maybe_async(sync_name = "helper_blocking", async_name = "helper_async")]
+    pub fn helper() -> u32 {
+        7
+    }
+
+    #[maybe_async]
+    pub fn twice() -> u32 {
+        helper().await * 2
+    }
+}
+
+#[test]
+fn sync_fn_names() {
+    let r = SyncReader { n: 3 };
+    assert_eq!(r.read_blocking(), 3);
+    assert_eq!(r.read_twice(), 6);
+    assert_eq!(r.get(), 3);
+    assert_eq!(SyncCounter { n: 2 }.count_twice(), 4);
+    assert_eq!(sync_m::helper_blocking(), 7);
+    assert_eq!(sync_m::twice(), 14);
+}
+
+#[test]
+fn async_fn_names() {
+    let r = AsyncReader { n: 3 };
+    assert_eq!(block_on(r.read()), 3);
+    assert_eq!(block_on(r.read_twice()), 6);
+    assert_eq!(block_on(r.fetch()), 3);
+    assert_eq!(block_on(AsyncCounter { n: 2 }.count_twice()), 4);
+    assert_eq!(block_on(async_m::helper_async()), 7);
+    assert_eq!(block_on(async_m::twice()), 14);
+}
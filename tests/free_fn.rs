@@ -0,0 +1,39 @@
+mod common;
+
+use common::block_on;
+use sync_async::sync_async;
+
+
+#[sync_async]
+pub struct Conn(pub u64);
+
+#[sync_async]
+#[maybe_async]
+pub fn fact(n: u64) -> u64 {
+    // 自分の名前は同じバリアントの関数を指す
+    if n == 0 {
+        return 1;
+    }
+    #[if_sync] let m = fact(n - 1);
+    #[if_async] let m = Box::pin(fact(n - 1)).await;
+    n * m
+}
+
+// 引数の use の別名も本体とシグネチャで使える
+#[sync_async(use(if_sync) crate::SyncConn as Conn, use(if_async) crate::AsyncConn as Conn)]
+#[maybe_async]
+pub fn open(n: u64) -> Conn {
+    Conn(n)
+}
+
+#[test]
+fn sync_free_fn() {
+    assert_eq!(sync_fact(5), 120);
+    assert_eq!(sync_open(3).0, 3);
+}
+
+#[test]
+fn async_free_fn() {
+    assert_eq!(block_on(async_fact(5)), 120);
+    assert_eq!(block_on(async_open(4)).0, 4);
+}
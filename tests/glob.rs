@@ -0,0 +1,52 @@
+mod common;
+
+use common::block_on;
+use sync_async::sync_async;
+
+
+pub mod blocking_prelude {
+    pub fn name() -> &'static str { "sync" }
+}
+
+pub mod async_prelude {
+    pub fn name() -> &'static str { "async" }
+}
+
+#[sync_async]
+pub mod kinds {
+    #[maybe_async]
+    pub fn kind() -> u8 { is_async!() as u8 }
+}
+
+// グロブは本体に注入されるだけで、シグネチャやドキュメントの書き換えには使われない
+#[sync_async(
+    use(if_sync) crate::blocking_prelude::*,
+    use(if_async) crate::async_prelude::*,
+    use(paired) crate::kinds::*,
+)]
+pub mod m {
+    /// [name] と [kind] を返す
+    #[maybe_async]
+    pub fn f() -> (&'static str, u8) { (name(), kind().await) }
+}
+
+#[sync_async]
+pub struct S;
+
+#[sync_async(use(if_sync) crate::blocking_prelude::*, use(if_async) crate::async_prelude::*)]
+impl S {
+    #[maybe_async]
+    pub fn g(&self) -> &'static str { name() }
+}
+
+#[test]
+fn sync_glob() {
+    assert_eq!(sync_m::f(), ("sync", 0));
+    assert_eq!(SyncS.g(), "sync");
+}
+
+#[test]
+fn async_glob() {
+    assert_eq!(block_on(async_m::f()), ("async", 1));
+    assert_eq!(block_on(AsyncS.g()), "async");
+}
@@ -0,0 +1,68 @@
+mod common;
+
+use common::block_on;
+use sync_async::sync_async;
+
+
+pub struct P {
+    pub a: u8,
+    pub b: u8,
+}
+
+#[sync_async]
+pub mod m {
+    use crate::P;
+
+    // #[if_sync] / #[if_async] は本体のどの深さにあってもよい
+    #[maybe_async]
+    pub fn arm(n: u8) -> u8 {
+        match n {
+            #[if_sync]
+            0 => 10,
+            #[if_async]
+            0 => 20,
+            _ => {
+                if n > 5 {
+                    #[if_sync] return 1;
+                    #[if_async] return 2;
+                }
+                n
+            },
+        }
+    }
+
+    #[maybe_async]
+    pub fn closure(n: u8) -> u8 {
+        let f = |x: u8| {
+            #[if_sync] let y = x + 1;
+            #[if_async] let y = x + 2;
+            y * 2
+        };
+        f(n)
+    }
+
+    #[maybe_async]
+    pub fn field() -> P {
+        P {
+            #[if_sync]
+            a: 1,
+            #[if_async]
+            a: 2,
+            b: 0,
+        }
+    }
+}
+
+#[test]
+fn sync_if_attrs() {
+    assert_eq!((sync_m::arm(0), sync_m::arm(9), sync_m::arm(3)), (10, 1, 3));
+    assert_eq!(sync_m::closure(1), 4);
+    assert_eq!(sync_m::field().a + sync_m::field().b, 1);
+}
+
+#[test]
+fn async_if_attrs() {
+    assert_eq!((block_on(async_m::arm(0)), block_on(async_m::arm(9)), block_on(async_m::arm(3))), (20, 2, 3));
+    assert_eq!(block_on(async_m::closure(1)), 6);
+    assert_eq!(block_on(async_m::field()).a, 2);
+}
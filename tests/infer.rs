@@ -0,0 +1,116 @@
+mod common;
+
+use common::block_on;
+use sync_async::sync_async;
+
+
+#[sync_async]
+pub struct S;
+
+#[sync_async(infer)]
+impl S {
+    pub fn selected(&self) -> u8 {
+        #[if_sync]
+        {
+            1
+        }
+        #[if_async]
+        {
+            2
+        }
+    }
+
+    pub fn awaited(&self) -> u8 {
+        self.selected().await + 1
+    }
+
+    pub fn variant(&self) -> bool {
+        is_async!()
+    }
+
+    // async ブロックの中の .await はこの関数の印に関係しない
+    pub fn async_block(&self) -> u8 {
+        let _f = async { std::future::ready(1).await };
+        3
+    }
+
+    // async クロージャの後の .await はこの関数のもの
+    pub fn after_closure(&self) -> u8 {
+        let _f = async |x: u8| x;
+        self.awaited().await
+    }
+
+    pub fn async_closure(&self) -> u8 {
+        let _f = async move || std::future::ready(1).await;
+        4
+    }
+
+    #[always_async]
+    pub fn marked(&self) -> u8 {
+        5
+    }
+}
+
+#[sync_async]
+pub struct R;
+
+// auto_await では .await を書かない呼び出しも推測に使う
+#[sync_async(infer, auto_await)]
+impl R {
+    pub fn leaf(&self) -> u8 {
+        #[if_sync]
+        {
+            1
+        }
+        #[if_async]
+        {
+            2
+        }
+    }
+
+    pub fn mid(&self) -> u8 {
+        self.leaf() + 1
+    }
+
+    pub fn top(&self) -> u8 {
+        assert!(Self::mid(self) > 0);
+        self.mid()
+    }
+
+    pub fn plain(&self) -> u8 {
+        9
+    }
+
+    // 外の関数の leaf() は auto_await の対象ではないので always_sync になる
+    pub fn outer(&self) -> u8 {
+        leaf(4)
+    }
+}
+
+pub fn leaf(x: u8) -> u8 {
+    x
+}
+
+#[test]
+fn sync_infer() {
+    assert_eq!(SyncS.awaited(), 2);
+    assert!(!SyncS.variant());
+    assert_eq!(SyncS.async_block(), 3);
+    assert_eq!(SyncS.after_closure(), 2);
+    assert_eq!(SyncS.async_closure(), 4);
+    assert_eq!(block_on(SyncS.marked()), 5);
+    assert_eq!(SyncR.top(), 2);
+    assert_eq!(SyncR.plain(), 9);
+}
+
+#[test]
+fn async_infer() {
+    assert_eq!(block_on(AsyncS.awaited()), 3);
+    assert!(block_on(AsyncS.variant()));
+    assert_eq!(AsyncS.async_block(), 3);
+    assert_eq!(block_on(AsyncS.after_closure()), 3);
+    assert_eq!(AsyncS.async_closure(), 4);
+    assert_eq!(block_on(AsyncR.top()), 3);
+    assert_eq!(AsyncR.plain(), 9);
+    assert_eq!(AsyncR.outer(), 4);
+}
@@ -0,0 +1,40 @@
+use sync_async::sync_async;
+
+
+#[sync_async]
+#[derive(Debug, PartialEq)]
+pub struct Error(pub u8);
+
+// 型の中の名前は use の引数で書き換わる
+#[sync_async(use(if_sync) crate::SyncError as Error, use(if_async) crate::AsyncError as Error)]
+pub type Result<T> = std::result::Result<T, Error>;
+
+#[sync_async(use(if_sync) crate::SyncError as Error, use(if_async) crate::AsyncError as Error)]
+pub const NOT_FOUND: Error = Error(4);
+
+#[sync_async(use(if_sync) crate::SyncError as Error, use(if_async) crate::AsyncError as Error)]
+pub static LAST: Option<Error> = None;
+
+#[sync_async(use(if_sync) crate::SyncError as Error, use(if_async) crate::AsyncError as Error)]
+pub union Slot {
+    pub code: u8,
+    pub err: std::mem::ManuallyDrop<Error>,
+}
+
+#[test]
+fn sync_items() {
+    let r: SyncResult<u8> = Err(SYNC_NOT_FOUND);
+    assert_eq!(r, Err(SyncError(4)));
+    assert_eq!(SYNC_LAST, None);
+    let s = SyncSlot { code: 1 };
+    assert_eq!(unsafe { s.code }, 1);
+}
+
+#[test]
+fn async_items() {
+    let r: AsyncResult<u8> = Err(ASYNC_NOT_FOUND);
+    assert_eq!(r, Err(AsyncError(4)));
+    assert_eq!(ASYNC_LAST, None);
+    let s = AsyncSlot { err: std::mem::ManuallyDrop::new(AsyncError(2)) };
+    assert_eq!(unsafe { &*s.err }, &AsyncError(2));
+}
@@ -0,0 +1,60 @@
+mod common;
+
+use common::block_on;
+use sync_async::sync_async;
+
+
+#[sync_async]
+pub struct Reader {
+    pub n: u32,
+}
+
+#[sync_async]
+pub enum Kind {
+    A,
+    B,
+}
+
+#[sync_async(use(if_sync) crate::SyncKind as Kind, use(if_async) crate::AsyncKind as Kind)]
+impl Reader {
+    #[maybe_async]
+    pub fn read(&self) -> u32 {
+        self.n
+    }
+
+    #[maybe_async]
+    pub fn kind(&self) -> Kind {
+        match self.n {
+            0 => Kind::A,
+            _ => Kind::B,
+        }
+    }
+
+    // マクロの引数の中の .await と名前も書き換える
+    #[maybe_async]
+    pub fn check(&self) -> Vec<u32> {
+        assert_eq!(self.read().await, self.n);
+        assert!(matches!(self.kind().await, Kind::B));
+        assert_eq!(self.read().await + select_variant!(1, 2), self.n + is_async!() as u32 + 1);
+        vec![self.read().await, self.read().await + 1]
+    }
+
+    #[maybe_async]
+    pub fn describe(&self) -> String {
+        format!("{}:{}", self.read().await, matches!(self.kind().await, Kind::A))
+    }
+}
+
+#[test]
+fn sync_macro_bodies() {
+    let r = SyncReader { n: 3 };
+    assert_eq!(r.check(), vec![3, 4]);
+    assert_eq!(r.describe(), "3:false");
+}
+
+#[test]
+fn async_macro_bodies() {
+    let r = AsyncReader { n: 3 };
+    assert_eq!(block_on(r.check()), vec![3, 4]);
+    assert_eq!(block_on(r.describe()), "3:false");
+}
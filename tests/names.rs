@@ -0,0 +1,43 @@
+mod common;
+
+use common::block_on;
+use sync_async::sync_async;
+
+
+#[sync_async(sync_name = "Blocking{}", async_name = "{}")]
+pub struct Client {
+    n: u32,
+}
+
+// impl の型も同じ書式で書き換わる
+#[sync_async(sync_name = "Blocking{}", async_name = "{}")]
+impl Client {
+    #[always_sync]
+    pub fn new(n: u32) -> Self { Self { n } }
+
+    #[maybe_async]
+    pub fn get(&self) -> u32 { self.n }
+}
+
+#[sync_async(sync_name = "blocking", async_name = "{}", sync_vis = pub(crate))]
+pub mod api {
+    #[maybe_async]
+    pub fn id(x: u32) -> u32 { x }
+}
+
+#[sync_async(sync_name = "BLOCKING_{}", async_name = "{}")]
+pub const LIMIT: u32 = 3;
+
+#[test]
+fn sync_names() {
+    assert_eq!(BlockingClient::new(1).get(), 1);
+    assert_eq!(blocking::id(2), 2);
+    assert_eq!(BLOCKING_LIMIT, 3);
+}
+
+#[test]
+fn async_names() {
+    assert_eq!(block_on(Client::new(1).get()), 1);
+    assert_eq!(block_on(api::id(2)), 2);
+    assert_eq!(LIMIT, 3);
+}
@@ -0,0 +1,61 @@
+mod common;
+
+use common::block_on;
+use sync_async::sync_async;
+
+
+pub mod backend {
+    pub fn name() -> &'static str { "backend" }
+}
+
+#[sync_async(use crate::backend)]
+pub mod conn {
+    pub struct Conn(pub u8);
+
+    // 印のない関数はそのまま残る
+    impl std::fmt::Display for Conn {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            write!(f, "conn{}", self.0)
+        }
+    }
+
+    impl Conn {
+        #[maybe_async]
+        pub fn id(&self) -> u8 { self.0 }
+
+        pub fn plain(&self) -> u8 { self.0 + 1 }
+    }
+
+    pub mod inner {
+        // 引数の use はネストしたモジュールにも注入される
+        #[maybe_async]
+        pub fn name() -> &'static str { backend::name() }
+
+        pub fn plain() -> u8 { 3 }
+    }
+
+    #[maybe_async]
+    pub fn top() -> u8 { 1 }
+}
+
+#[test]
+fn sync_nested_items() {
+    let c = sync_conn::Conn(1);
+    assert_eq!(c.to_string(), "conn1");
+    assert_eq!(c.id(), 1);
+    assert_eq!(c.plain(), 2);
+    assert_eq!(sync_conn::inner::name(), "backend");
+    assert_eq!(sync_conn::inner::plain(), 3);
+    assert_eq!(sync_conn::top(), 1);
+}
+
+#[test]
+fn async_nested_items() {
+    let c = async_conn::Conn(2);
+    assert_eq!(c.to_string(), "conn2");
+    assert_eq!(block_on(c.id()), 2);
+    assert_eq!(c.plain(), 3);
+    assert_eq!(block_on(async_conn::inner::name()), "backend");
+    assert_eq!(async_conn::inner::plain(), 3);
+    assert_eq!(block_on(async_conn::top()), 1);
+}
@@ -0,0 +1,59 @@
+use sync_async::sync_async;
+
+
+pub mod net {
+    use sync_async::sync_async;
+
+    #[sync_async]
+    pub struct Connection {
+        pub id: u32,
+    }
+
+    #[sync_async(sync_name = "Blocking{}", async_name = "{}")]
+    pub struct Conn {
+        pub n: u8,
+    }
+}
+
+#[sync_async]
+pub mod utils {
+    #[maybe_async]
+    pub fn id(x: u32) -> u32 { x }
+}
+
+#[sync_async]
+pub const LIMIT: u32 = 7;
+
+#[sync_async(use(paired) crate::{net::Connection, utils, LIMIT as MAX})]
+pub struct Client {
+    conn: Connection,
+}
+
+#[sync_async(use(paired) crate::{net::Connection, utils, LIMIT as MAX})]
+impl Client {
+    /// [Connection]
+    #[maybe_async]
+    pub fn id(&self) -> u32 {
+        utils::id(self.conn.id).await + MAX
+    }
+}
+
+// use(paired) の名前は sync_name / async_name の書式に従う
+#[sync_async(sync_name = "Blocking{}", async_name = "{}", use(paired) crate::net::Conn)]
+pub struct Pool {
+    pub conn: Conn,
+}
+
+#[test]
+fn paired_imports() {
+    let c = SyncClient { conn: net::SyncConnection { id: 1 } };
+    assert_eq!(c.id(), 8);
+    let _ = AsyncClient { conn: net::AsyncConnection { id: 1 } };
+}
+
+#[test]
+fn paired_names_follow_templates() {
+    let p = BlockingPool { conn: net::BlockingConn { n: 1 } };
+    let _ = Pool { conn: net::Conn { n: 2 } };
+    assert_eq!(p.conn.n, 1);
+}
@@ -0,0 +1,46 @@
+mod common;
+
+use common::block_on;
+use sync_async::sync_async;
+
+
+pub mod sio {
+    pub fn read(n: u8) -> u8 { n }
+    pub struct File(pub u8);
+}
+
+pub mod aio {
+    pub async fn read(n: u8) -> u8 { n + 1 }
+    pub struct File(pub u8);
+}
+
+// 本体、シグネチャ、フィールドの型、ドキュメントのパスの先頭が書き換わる
+#[sync_async(rewrite(if_sync) crate::aio => crate::sio)]
+pub mod m {
+    pub struct H {
+        pub f: crate::aio::File,
+    }
+
+    /// [crate::aio::read] を呼ぶ
+    #[maybe_async]
+    pub fn f(h: &H) -> u8 { crate::aio::read(h.f.0).await }
+}
+
+#[sync_async(rewrite(if_sync) crate::aio => crate::sio)]
+pub struct S {
+    pub f: crate::aio::File,
+}
+
+#[test]
+fn sync_rewrite() {
+    let h = sync_m::H { f: sio::File(1) };
+    assert_eq!(sync_m::f(&h), 1);
+    let _ = SyncS { f: sio::File(0) };
+}
+
+#[test]
+fn async_rewrite() {
+    let h = async_m::H { f: aio::File(1) };
+    assert_eq!(block_on(async_m::f(&h)), 2);
+    let _ = AsyncS { f: aio::File(0) };
+}
@@ -0,0 +1,60 @@
+mod common;
+
+use common::block_on;
+use sync_async::sync_async;
+
+
+#[sync_async]
+pub mod variant {
+    #[maybe_async]
+    pub fn name() -> &'static str {
+        select_variant!("sync", "async")
+    }
+
+    #[maybe_async]
+    pub fn is_async() -> bool {
+        is_async!()
+    }
+
+    // 他のマクロの引数の中でも置き換える
+    #[maybe_async]
+    pub fn formatted() -> String {
+        format!("{}-{}", select_variant!("sync", "async"), is_async!())
+    }
+
+    // {} の select_variant! は文の途中でも末尾の式にならない
+    #[maybe_async]
+    pub fn statement() -> u8 {
+        let mut n = 0;
+        select_variant!{ n += 1, n += 2 }
+        n
+    }
+
+    #[maybe_async]
+    pub fn fact(n: u64) -> u64 {
+        if n == 0 {
+            1
+        }
+        else {
+            n * select_variant!(fact(n - 1), Box::pin(fact(n - 1)).await)
+        }
+    }
+}
+
+#[test]
+fn sync_variant() {
+    assert_eq!(sync_variant::name(), "sync");
+    assert!(!sync_variant::is_async());
+    assert_eq!(sync_variant::formatted(), "sync-false");
+    assert_eq!(sync_variant::statement(), 1);
+    assert_eq!(sync_variant::fact(5), 120);
+}
+
+#[test]
+fn async_variant() {
+    assert_eq!(block_on(async_variant::name()), "async");
+    assert!(block_on(async_variant::is_async()));
+    assert_eq!(block_on(async_variant::formatted()), "async-true");
+    assert_eq!(block_on(async_variant::statement()), 2);
+    assert_eq!(block_on(async_variant::fact(5)), 120);
+}
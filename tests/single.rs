@@ -0,0 +1,48 @@
+mod common;
+
+use common::block_on;
+use sync_async::sync_async;
+
+
+// async_if が偽なので sync のバリアントだけが元の名前で出力される
+#[sync_async(async_if = any())]
+pub struct Foo {
+    n: u8,
+}
+
+#[sync_async(async_if = any(), use(if_async) crate::missing::Thing)]
+impl Foo {
+    /// [Foo] を作る
+    #[maybe_async]
+    pub fn new(n: u8) -> Foo {
+        #[if_async] let _: Thing;
+        Foo { n }
+    }
+
+    #[maybe_async]
+    pub fn read(&self) -> u8 { self.n + Self::new(1).await.n }
+}
+
+#[sync_async(async_if = any())]
+pub mod s {
+    #[maybe_async]
+    pub fn g() -> u8 { is_async!() as u8 }
+}
+
+// async_if が真なら async のバリアントになる
+#[sync_async(async_if = all())]
+pub mod a {
+    #[maybe_async]
+    pub fn g() -> u8 { is_async!() as u8 }
+}
+
+#[test]
+fn single_sync() {
+    assert_eq!(Foo::new(1).read(), 2);
+    assert_eq!(s::g(), 0);
+}
+
+#[test]
+fn single_async() {
+    assert_eq!(block_on(a::g()), 1);
+}
@@ -0,0 +1,64 @@
+mod common;
+
+use common::block_on;
+use sync_async::sync_async;
+
+
+pub trait Blocking {
+    fn n(&self) -> u8;
+    fn kind(&self) -> &'static str { "blocking" }
+}
+
+pub trait NonBlocking {
+    fn n(&self) -> u8;
+    fn kind(&self) -> &'static str { "non-blocking" }
+}
+
+#[sync_async]
+pub struct Conn(pub u8);
+
+// トレイトのパスも use の別名で書き換わる
+#[sync_async(
+    use(if_sync) crate::Blocking as Kind,
+    use(if_async) crate::NonBlocking as Kind,
+)]
+impl Kind for Conn {
+    #[always_sync]
+    fn n(&self) -> u8 { self.0 }
+}
+
+#[sync_async]
+impl std::fmt::Display for Conn {
+    #[always_sync]
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "conn{}", self.0)
+    }
+}
+
+#[sync_async]
+impl From<u8> for Conn {
+    #[always_sync]
+    fn from(n: u8) -> Self { Self(n) }
+}
+
+#[sync_async]
+impl Conn {
+    #[maybe_async]
+    pub fn id(&self) -> u8 { self.0 }
+}
+
+#[test]
+fn sync_trait_impl() {
+    let c = SyncConn::from(1);
+    assert_eq!((Blocking::kind(&c), Blocking::n(&c)), ("blocking", 1));
+    assert_eq!(c.to_string(), "conn1");
+    assert_eq!(c.id(), 1);
+}
+
+#[test]
+fn async_trait_impl() {
+    let c = AsyncConn::from(2);
+    assert_eq!((NonBlocking::kind(&c), NonBlocking::n(&c)), ("non-blocking", 2));
+    assert_eq!(c.to_string(), "conn2");
+    assert_eq!(block_on(c.id()), 2);
+}
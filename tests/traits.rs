@@ -0,0 +1,42 @@
+mod common;
+
+use common::block_on;
+use sync_async::sync_async;
+
+
+#[sync_async]
+pub trait Store {
+    #[maybe_async]
+    fn get(&self, k: &str) -> Option<u8>;
+
+    // 既定の本体も impl のメソッドと同じように変換される
+    #[maybe_async]
+    fn get_or(&self, k: &str, d: u8) -> u8 { self.get(k).await.unwrap_or(d) }
+
+    #[always_sync]
+    fn name(&self) -> &'static str { "store" }
+}
+
+pub struct Mem;
+
+impl SyncStore for Mem {
+    fn get(&self, k: &str) -> Option<u8> { (k == "a").then_some(1) }
+}
+
+impl AsyncStore for Mem {
+    async fn get(&self, k: &str) -> Option<u8> { (k == "a").then_some(2) }
+}
+
+#[test]
+fn sync_trait() {
+    assert_eq!(SyncStore::get_or(&Mem, "a", 0), 1);
+    assert_eq!(SyncStore::get_or(&Mem, "b", 0), 0);
+    assert_eq!(SyncStore::name(&Mem), "store");
+}
+
+#[test]
+fn async_trait() {
+    assert_eq!(block_on(AsyncStore::get_or(&Mem, "a", 0)), 2);
+    assert_eq!(block_on(AsyncStore::get_or(&Mem, "b", 0)), 0);
+    assert_eq!(AsyncStore::name(&Mem), "store");
+}
@@ -0,0 +1,49 @@
+mod common;
+
+use common::block_on;
+use sync_async::sync_async;
+
+
+#[sync_async(
+    type(if_sync) Lock<T> = std::cell::RefCell<T>,
+    type(if_async) Lock<T> = std::sync::Mutex<T>,
+    type Bytes = Vec<u8>,
+)]
+pub struct S {
+    pub l: Lock<Bytes>,
+    pub n: Lock<u32>,
+}
+
+#[sync_async(
+    type(if_sync) Lock<T> = std::cell::RefCell<T>,
+    type(if_async) Lock<T> = std::sync::Mutex<T>,
+    type Bytes = Vec<u8>,
+)]
+impl S {
+    // 式の中の Lock::new も別名の型になる
+    #[maybe_async]
+    pub fn new(b: Bytes) -> Self {
+        Self { l: Lock::new(b), n: Lock::<u32>::new(1) }
+    }
+
+    #[maybe_async]
+    pub fn take(self) -> Bytes {
+        select_variant!(self.l.into_inner(), self.l.into_inner().unwrap())
+    }
+}
+
+#[test]
+fn sync_type_alias() {
+    let s = SyncS::new(vec![1, 2]);
+    let _: &std::cell::RefCell<Vec<u8>> = &s.l;
+    let _: &std::cell::RefCell<u32> = &s.n;
+    assert_eq!(s.take(), vec![1, 2]);
+}
+
+#[test]
+fn async_type_alias() {
+    let s = block_on(AsyncS::new(vec![3]));
+    let _: &std::sync::Mutex<Vec<u8>> = &s.l;
+    let _: &std::sync::Mutex<u32> = &s.n;
+    assert_eq!(block_on(s.take()), vec![3]);
+}
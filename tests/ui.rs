@@ -0,0 +1,6 @@
+// エラーになる引数や関数のメッセージを確かめる
+#[test]
+fn ui() {
+    let t = trybuild::TestCases::new();
+    t.compile_fail("tests/ui/*.rs");
+}
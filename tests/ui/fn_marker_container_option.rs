@@ -0,0 +1,12 @@
+use sync_async::sync_async;
+
+#[sync_async]
+pub struct S;
+
+#[sync_async]
+impl S {
+    #[maybe_async(auto_await)]
+    pub fn f(&self) {}
+}
+
+fn main() {}
@@ -0,0 +1,6 @@
+use sync_async::sync_async;
+
+#[sync_async(sync_name = "blocking_{}")]
+pub struct S;
+
+fn main() {}
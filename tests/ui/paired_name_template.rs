@@ -0,0 +1,12 @@
+use sync_async::sync_async;
+
+#[sync_async]
+pub mod utils {
+    #[maybe_async]
+    pub fn id(x: u32) -> u32 { x }
+}
+
+#[sync_async(sync_name = "Blocking{}", async_name = "{}", use(paired) crate::utils)]
+pub struct S;
+
+fn main() {}
@@ -0,0 +1,11 @@
+use sync_async::sync_async;
+
+#[sync_async]
+pub struct S;
+
+#[sync_async]
+impl S {
+    pub fn f(&self) {}
+}
+
+fn main() {}